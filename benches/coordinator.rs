@@ -0,0 +1,214 @@
+//! Coordinator throughput for large groups: the latency of each message the coordinator receives,
+//! and the time to sign end to end as the group, threshold and fraction of malicious signers grow.
+use std::time::Duration;
+
+use criterion::{
+    criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, SamplingMode, Throughput,
+};
+use rand::{rngs::StdRng, SeedableRng};
+use schnorr_fun::{
+    frost::{self, Frost, FrostKey},
+    fun::{
+        marker::{EvenY, Public, Zero},
+        s, Scalar,
+    },
+    nonce::Deterministic,
+    Message,
+};
+use sha2::Sha256;
+
+use roast::{
+    adversary::Adversary,
+    coordinator::Coordinator,
+    protocol::{CoordinatorMessage, SchemeSignerMessage},
+    signer::RoastSigner,
+    simulator::Simulation,
+};
+
+type BenchFrost = Frost<Sha256, Deterministic<Sha256>>;
+type BenchSigner = RoastSigner<BenchFrost, FrostKey<EvenY>>;
+type BenchSignerMessage = SchemeSignerMessage<BenchFrost, FrostKey<EvenY>>;
+
+const GROUP_SIZES: [usize; 3] = [50, 200, 500];
+
+/// Deal key shares for `n` signers from a single polynomial
+///
+/// Every party contributes the same polynomial, as a full simulated keygen takes minutes for
+/// hundreds of signers.
+fn deal_keys(frost: &BenchFrost, threshold: usize, n: usize) -> (FrostKey<EvenY>, Vec<Scalar>) {
+    let mut rng = StdRng::seed_from_u64(n as u64);
+    let scalar_poly = frost::generate_scalar_poly(threshold, &mut rng);
+    let point_poly = frost::to_point_poly(&scalar_poly);
+    let keygen = frost.new_keygen(vec![point_poly; n]).unwrap();
+    let (shares, proof_of_possession) = frost.create_shares(&keygen, scalar_poly);
+    let secret_shares = shares
+        .iter()
+        .map(|share| {
+            let n = Scalar::<Public, Zero>::from(n as u32);
+            s!(n * share).non_zero().expect("share is non-zero")
+        })
+        .collect();
+    let (_, frost_key) = frost
+        .finish_keygen(
+            keygen,
+            0,
+            vec![shares[0].clone(); n],
+            vec![proof_of_possession; n],
+        )
+        .unwrap();
+    (frost_key.into_xonly_key(), secret_shares)
+}
+
+fn new_signers(
+    frost: &BenchFrost,
+    frost_key: &FrostKey<EvenY>,
+    secret_shares: &[Scalar],
+    message: Message,
+) -> Vec<(BenchSigner, BenchSignerMessage)> {
+    let mut rng = rand::thread_rng();
+    secret_shares
+        .iter()
+        .enumerate()
+        .map(|(i, secret_share)| {
+            RoastSigner::new(
+                &mut rng,
+                frost.clone(),
+                frost_key.clone(),
+                i,
+                secret_share.clone(),
+                message,
+            )
+        })
+        .collect()
+}
+
+/// The cost of each nonce and signature share the coordinator receives
+///
+/// Signers' first nonces arrive in index order, so the first sign session is always made up of
+/// the first `t` signers and their signature shares can be replayed into a fresh coordinator.
+fn receive(c: &mut Criterion) {
+    let frost = BenchFrost::default();
+    let message = Message::plain("bench", b"coordinator receive");
+
+    let mut group = c.benchmark_group("receive");
+    group.sample_size(10).sampling_mode(SamplingMode::Flat);
+    for n in GROUP_SIZES {
+        let threshold = n / 2 + 1;
+        let (frost_key, secret_shares) = deal_keys(&frost, threshold, n);
+        let (mut signers, nonces): (Vec<_>, Vec<_>) =
+            new_signers(&frost, &frost_key, &secret_shares, message)
+                .into_iter()
+                .unzip();
+        let new_coordinator = || {
+            let coordinator = Coordinator::new(frost.clone(), frost_key.clone());
+            coordinator.start_session(message).unwrap();
+            coordinator
+        };
+
+        let coordinator = new_coordinator();
+        let mut sign_request = None;
+        for (i, nonce) in nonces.iter().enumerate() {
+            let response = coordinator.receive(i, nonce.clone()).unwrap();
+            if let Some(message @ CoordinatorMessage::SignRequest { .. }) = response.message {
+                sign_request = Some((response.recipients, message));
+            }
+        }
+        let (signers_asked, sign_request) = sign_request.expect("a sign session was opened");
+        let mut rng = rand::thread_rng();
+        let shares = signers_asked
+            .into_iter()
+            .map(|i| {
+                let share = signers[i]
+                    .receive(&mut rng, sign_request.clone())
+                    .unwrap()
+                    .expect("signer replies with a share");
+                (i, share)
+            })
+            .collect::<Vec<_>>();
+
+        group.throughput(Throughput::Elements(n as u64));
+        group.bench_with_input(BenchmarkId::new("nonce", n), &nonces, |b, nonces| {
+            b.iter_batched(
+                new_coordinator,
+                |coordinator| {
+                    for (i, nonce) in nonces.iter().enumerate() {
+                        coordinator.receive(i, nonce.clone()).unwrap();
+                    }
+                },
+                BatchSize::PerIteration,
+            )
+        });
+
+        group.throughput(Throughput::Elements(shares.len() as u64));
+        group.bench_with_input(BenchmarkId::new("share", n), &shares, |b, shares| {
+            b.iter_batched(
+                || {
+                    let coordinator = new_coordinator();
+                    for (i, nonce) in nonces.iter().enumerate() {
+                        coordinator.receive(i, nonce.clone()).unwrap();
+                    }
+                    coordinator
+                },
+                |coordinator| {
+                    for (i, share) in shares {
+                        coordinator.receive(*i, share.clone()).unwrap();
+                    }
+                    assert!(coordinator.result().is_some());
+                },
+                BatchSize::PerIteration,
+            )
+        });
+    }
+    group.finish();
+}
+
+/// The time to sign over a network delivering every message after a tick, with the lowest
+/// indexed signers sending invalid signature shares
+fn sign(c: &mut Criterion) {
+    let frost = BenchFrost::default();
+    let message = Message::plain("bench", b"coordinator sign");
+
+    let mut group = c.benchmark_group("sign");
+    group
+        .sample_size(10)
+        .sampling_mode(SamplingMode::Flat)
+        .measurement_time(Duration::from_secs(20));
+    for n in GROUP_SIZES {
+        for threshold in [n / 2 + 1, 2 * n / 3 + 1] {
+            let (frost_key, secret_shares) = deal_keys(&frost, threshold, n);
+            for malicious_percent in [0, 25] {
+                let n_malicious = n * malicious_percent / 100;
+                let id = BenchmarkId::new(
+                    format!("{malicious_percent}%_malicious"),
+                    format!("{threshold}-of-{n}"),
+                );
+                group.bench_function(id, |b| {
+                    b.iter_batched(
+                        || {
+                            let coordinator = Coordinator::new(frost.clone(), frost_key.clone());
+                            coordinator.start_session(message).unwrap();
+                            let signers = new_signers(&frost, &frost_key, &secret_shares, message);
+                            (coordinator, signers)
+                        },
+                        |(coordinator, signers)| {
+                            let mut simulation = Simulation::new(coordinator, signers, 0);
+                            for i in 0..n_malicious {
+                                simulation = simulation.with_adversary(
+                                    i,
+                                    Adversary::InvalidShare(Scalar::<Public, _>::one().mark_zero()),
+                                );
+                            }
+                            let report = simulation.run();
+                            assert!(report.signature.is_some());
+                        },
+                        BatchSize::PerIteration,
+                    )
+                });
+            }
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, receive, sign);
+criterion_main!(benches);
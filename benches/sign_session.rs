@@ -0,0 +1,80 @@
+//! Verifying every signature share of a sign session, either preparing the session once and
+//! reusing it (as the coordinator does) or preparing it again for each share.
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use schnorr_fun::{frost::Frost, nonce::Deterministic, Message};
+use sha2::Sha256;
+
+use roast::{coordinator::StoredMessage, threshold_scheme::ThresholdScheme};
+
+fn verify_shares(c: &mut Criterion) {
+    let frost = Frost::<Sha256, Deterministic<Sha256>>::default();
+    let mut rng = rand::thread_rng();
+    let message = StoredMessage::from(Message::plain("bench", b"sign session"));
+
+    let mut group = c.benchmark_group("verify_shares");
+    for threshold in [5, 20, 50] {
+        let (frost_key, secret_shares) = frost.simulate_keygen(threshold, threshold, &mut rng);
+        let joint_key = frost_key.into_xonly_key();
+
+        let secret_nonces = (0..threshold)
+            .map(|_| ThresholdScheme::gen_nonce(&frost, &mut rng))
+            .collect::<Vec<_>>();
+        let nonces = secret_nonces
+            .iter()
+            .enumerate()
+            .map(|(i, nonce)| (i, ThresholdScheme::public_nonce(&frost, nonce)))
+            .collect::<Vec<_>>();
+        let session =
+            ThresholdScheme::start_sign_session(&frost, &joint_key, nonces.clone(), &message);
+        let shares = secret_nonces
+            .into_iter()
+            .enumerate()
+            .map(|(i, nonce)| {
+                ThresholdScheme::sign(&frost, &joint_key, &session, i, &secret_shares[i], nonce)
+            })
+            .collect::<Vec<_>>();
+
+        group.bench_with_input(
+            BenchmarkId::new("prepared_once", threshold),
+            &shares,
+            |b, shares| {
+                b.iter(|| {
+                    let session = ThresholdScheme::start_sign_session(
+                        &frost,
+                        &joint_key,
+                        nonces.clone(),
+                        &message,
+                    );
+                    for (i, share) in shares.iter().enumerate() {
+                        assert!(ThresholdScheme::verify_signature_share(
+                            &frost, &joint_key, &session, i, *share
+                        ));
+                    }
+                })
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("prepared_per_share", threshold),
+            &shares,
+            |b, shares| {
+                b.iter(|| {
+                    for (i, share) in shares.iter().enumerate() {
+                        let session = ThresholdScheme::start_sign_session(
+                            &frost,
+                            &joint_key,
+                            nonces.clone(),
+                            &message,
+                        );
+                        assert!(ThresholdScheme::verify_signature_share(
+                            &frost, &joint_key, &session, i, *share
+                        ));
+                    }
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, verify_shares);
+criterion_main!(benches);
@@ -0,0 +1,121 @@
+//! ROAST Adversaries
+//!
+//! Ways a faulty or malicious signer can deviate from the protocol, for testing that a
+//! [`Coordinator`] still obtains a signature. ROAST guarantees that with at most `n - t` such
+//! signers, no more than `n - t + 1` sign sessions are ever opened.
+//!
+//! An [`Adversary`] is given to a [`Simulation`] to control a signer, or can be used to tamper
+//! with an honest signer's messages in any other harness.
+//!
+//! [`Coordinator`]: crate::coordinator::Coordinator
+//! [`Simulation`]: crate::simulator::Simulation
+use schnorr_fun::musig::Nonce;
+use secp256kfun::{
+    marker::{Public, Zero},
+    Scalar,
+};
+
+use crate::{protocol::SignerMessage, threshold_scheme::ThresholdScheme};
+
+/// A deviation from the protocol
+#[derive(Debug, Clone, PartialEq)]
+pub enum Adversary<N = Nonce, Z = Scalar<Public, Zero>> {
+    /// Never sends anything, not even a first nonce
+    Withhold,
+    /// Sends every message this many ticks late
+    RespondLate(u64),
+    /// Sends this share in place of each of its signature shares
+    InvalidShare(Z),
+    /// Sends an extra nonce after each message, while the coordinator is not expecting one
+    Unsolicited,
+    /// Labels each signature share with a later session than the one it was asked to sign in
+    WrongSession,
+    /// Shares its first nonce again in place of each new nonce
+    ReuseNonce,
+    /// Shares this nonce, whose secrets it does not hold, in place of every nonce
+    ForeignNonce(N),
+    /// Shares a first nonce, then never replies to a sign request
+    SilentAfterNonce,
+}
+
+/// The [`Adversary`] of a signer using the threshold scheme `S`
+pub type SchemeAdversary<S, K> =
+    Adversary<<S as ThresholdScheme<K>>::PublicNonce, <S as ThresholdScheme<K>>::SignatureShare>;
+
+impl<N: Clone, Z: Clone> Adversary<N, Z> {
+    /// Whether the adversary sends its first nonce
+    pub fn sends_first_nonce(&self) -> bool {
+        !matches!(self, Adversary::Withhold)
+    }
+
+    /// Whether the adversary replies to sign requests
+    pub fn replies(&self) -> bool {
+        !matches!(self, Adversary::Withhold | Adversary::SilentAfterNonce)
+    }
+
+    /// How long the adversary holds back each message
+    pub fn delay(&self) -> u64 {
+        match self {
+            Adversary::RespondLate(delay) => *delay,
+            _ => 0,
+        }
+    }
+
+    /// Tamper with a message produced by an honest signer
+    ///
+    /// `first_nonce` is the first nonce the signer shared.
+    pub fn tamper(&self, message: SignerMessage<N, Z>, first_nonce: &N) -> SignerMessage<N, Z> {
+        match (self, message) {
+            (
+                Adversary::InvalidShare(share),
+                SignerMessage::ShareAndNonce {
+                    message_id,
+                    session_id,
+                    nonce,
+                    ..
+                },
+            ) => SignerMessage::ShareAndNonce {
+                message_id,
+                session_id,
+                signature_share: share.clone(),
+                nonce,
+            },
+            (
+                Adversary::WrongSession,
+                SignerMessage::ShareAndNonce {
+                    message_id,
+                    session_id,
+                    signature_share,
+                    nonce,
+                },
+            ) => SignerMessage::ShareAndNonce {
+                message_id,
+                session_id: session_id + 1,
+                signature_share,
+                nonce,
+            },
+            (Adversary::ReuseNonce, message) => with_nonce(message, first_nonce.clone()),
+            (Adversary::ForeignNonce(nonce), message) => with_nonce(message, nonce.clone()),
+            (_, message) => message,
+        }
+    }
+}
+
+fn with_nonce<N, Z>(message: SignerMessage<N, Z>, nonce: N) -> SignerMessage<N, Z> {
+    match message {
+        SignerMessage::NonceOnly { message_id, .. } => {
+            SignerMessage::NonceOnly { message_id, nonce }
+        }
+        SignerMessage::ShareAndNonce {
+            message_id,
+            session_id,
+            signature_share,
+            ..
+        } => SignerMessage::ShareAndNonce {
+            message_id,
+            session_id,
+            signature_share,
+            nonce,
+        },
+    }
+}
@@ -0,0 +1,196 @@
+//! ROAST Session Announcements
+//!
+//! A dishonest coordinator could equivocate by sending different nonce sets to different signers
+//! for the same sign session. A coordinator with an announcement key signs the nonce set of every
+//! [`CoordinatorMessage::SignRequest`], making each request a [`SessionAnnouncement`] that signers
+//! can pass on to one another.
+//!
+//! Two validly signed announcements of different nonce sets for the same session and message are
+//! an [`EquivocationProof`] which anyone holding the coordinator's public key can check.
+//!
+//! Announcements are signed with an [`AnnouncementScheme`], which is independent of the threshold
+//! scheme being coordinated. By default they are Schnorr signatures over secp256k1, see
+//! [`SchnorrAnnouncements`]. The nonces and message are signed in their JSON encoding.
+//!
+//! [`CoordinatorMessage::SignRequest`]: crate::protocol::CoordinatorMessage::SignRequest
+use std::fmt::Debug;
+
+use schnorr_fun::{musig::Nonce, nonce::Deterministic, Message, Schnorr, Signature};
+use secp256kfun::{
+    marker::{EvenY, Public},
+    Point, XOnlyKeyPair,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sha2::Sha256;
+
+use crate::{coordinator::StoredMessage, threshold_scheme::ThresholdScheme};
+
+const ANNOUNCEMENT_TAG: &str = "roast/session-announcement";
+
+/// A signature scheme for a coordinator to sign its session announcements with
+pub trait AnnouncementScheme {
+    /// The key a coordinator signs its [`SessionAnnouncement`]s with
+    type AnnouncementKey;
+    /// The public key signers check a coordinator's announcements against
+    type CoordinatorKey: Clone + Debug;
+    /// A coordinator's signature over a session announcement
+    type AnnouncementSignature: Clone + PartialEq + Debug + Serialize + DeserializeOwned;
+
+    /// Sign the encoding of a session announcement with the coordinator's key
+    fn sign_announcement(
+        &self,
+        announcement_key: &Self::AnnouncementKey,
+        announcement: &[u8],
+    ) -> Self::AnnouncementSignature;
+
+    /// Check the encoding of a session announcement was signed by the coordinator
+    fn verify_announcement(
+        &self,
+        coordinator_key: &Self::CoordinatorKey,
+        announcement: &[u8],
+        signature: &Self::AnnouncementSignature,
+    ) -> bool;
+}
+
+/// Announcements signed with Schnorr signatures under an x-only coordinator key
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SchnorrAnnouncements;
+
+impl AnnouncementScheme for SchnorrAnnouncements {
+    type AnnouncementKey = XOnlyKeyPair;
+    type CoordinatorKey = Point<EvenY>;
+    type AnnouncementSignature = Signature;
+
+    fn sign_announcement(&self, announcement_key: &XOnlyKeyPair, announcement: &[u8]) -> Signature {
+        schnorr().sign(
+            announcement_key,
+            Message::<Public>::plain(ANNOUNCEMENT_TAG, announcement),
+        )
+    }
+
+    fn verify_announcement(
+        &self,
+        coordinator_key: &Point<EvenY>,
+        announcement: &[u8],
+        signature: &Signature,
+    ) -> bool {
+        schnorr().verify(
+            coordinator_key,
+            Message::<Public>::plain(ANNOUNCEMENT_TAG, announcement),
+            signature,
+        )
+    }
+}
+
+fn schnorr() -> Schnorr<Sha256, Deterministic<Sha256>> {
+    Schnorr::default()
+}
+
+/// A coordinator's signed statement of the nonce set of a sign session
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionAnnouncement<N = Nonce, M = StoredMessage, A = Signature> {
+    pub message: M,
+    pub session_id: usize,
+    pub nonce_set: Vec<(usize, N)>,
+    pub signature: A,
+}
+
+impl<N: Clone + PartialEq + Serialize, M: PartialEq + Serialize, A> SessionAnnouncement<N, M, A> {
+    /// Sign an announcement with the coordinator's key
+    pub fn sign<T: AnnouncementScheme<AnnouncementSignature = A>>(
+        announcement_scheme: &T,
+        announcement_key: &T::AnnouncementKey,
+        message: M,
+        session_id: usize,
+        nonce_set: Vec<(usize, N)>,
+    ) -> Self {
+        let bytes = announcement_bytes(&message, session_id, &nonce_set);
+        let signature = announcement_scheme.sign_announcement(announcement_key, &bytes);
+        Self {
+            message,
+            session_id,
+            nonce_set,
+            signature,
+        }
+    }
+
+    /// Check the announcement was signed by the coordinator
+    pub fn verify<T: AnnouncementScheme<AnnouncementSignature = A>>(
+        &self,
+        announcement_scheme: &T,
+        coordinator_key: &T::CoordinatorKey,
+    ) -> bool {
+        let bytes = announcement_bytes(&self.message, self.session_id, &self.nonce_set);
+        announcement_scheme.verify_announcement(coordinator_key, &bytes, &self.signature)
+    }
+
+    /// Whether the two announcements are for the same session but disagree on its nonce set
+    pub fn conflicts_with(&self, other: &SessionAnnouncement<N, M, A>) -> bool {
+        self.message == other.message
+            && self.session_id == other.session_id
+            && sorted(&self.nonce_set) != sorted(&other.nonce_set)
+    }
+}
+
+/// The [`SessionAnnouncement`] of the threshold scheme `S`, signed with the announcement scheme `T`
+pub type SchemeAnnouncement<S, K, T = SchnorrAnnouncements> = SessionAnnouncement<
+    <S as ThresholdScheme<K>>::PublicNonce,
+    <S as ThresholdScheme<K>>::Message,
+    <T as AnnouncementScheme>::AnnouncementSignature,
+>;
+
+/// Evidence that a coordinator announced two different nonce sets for the same sign session
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EquivocationProof<N = Nonce, M = StoredMessage, A = Signature> {
+    pub first: SessionAnnouncement<N, M, A>,
+    pub second: SessionAnnouncement<N, M, A>,
+}
+
+impl<N: Clone + PartialEq + Serialize, M: PartialEq + Serialize, A> EquivocationProof<N, M, A> {
+    /// Check that the coordinator signed both conflicting announcements
+    pub fn verify<T: AnnouncementScheme<AnnouncementSignature = A>>(
+        &self,
+        announcement_scheme: &T,
+        coordinator_key: &T::CoordinatorKey,
+    ) -> bool {
+        self.first.conflicts_with(&self.second)
+            && self.first.verify(announcement_scheme, coordinator_key)
+            && self.second.verify(announcement_scheme, coordinator_key)
+    }
+}
+
+/// The [`EquivocationProof`] of the threshold scheme `S`, signed with the announcement scheme `T`
+pub type SchemeEquivocationProof<S, K, T = SchnorrAnnouncements> = EquivocationProof<
+    <S as ThresholdScheme<K>>::PublicNonce,
+    <S as ThresholdScheme<K>>::Message,
+    <T as AnnouncementScheme>::AnnouncementSignature,
+>;
+
+fn sorted<N: Clone>(nonce_set: &[(usize, N)]) -> Vec<(usize, N)> {
+    let mut nonce_set = nonce_set.to_vec();
+    nonce_set.sort_by_key(|(index, _)| *index);
+    nonce_set
+}
+
+/// The signed bytes, which do not depend on the order of the nonce set
+fn announcement_bytes<N: Clone + Serialize, M: Serialize>(
+    message: &M,
+    session_id: usize,
+    nonce_set: &[(usize, N)],
+) -> Vec<u8> {
+    let mut bytes = vec![];
+    push_json(&mut bytes, message);
+    bytes.extend_from_slice(&(session_id as u64).to_be_bytes());
+    for (index, nonce) in sorted(nonce_set) {
+        bytes.extend_from_slice(&(index as u64).to_be_bytes());
+        push_json(&mut bytes, &nonce);
+    }
+    bytes
+}
+
+/// Append the length prefixed JSON encoding of a value
+fn push_json(bytes: &mut Vec<u8>, value: &impl Serialize) {
+    let json = serde_json::to_vec(value).expect("scheme types serialize to JSON");
+    bytes.extend_from_slice(&(json.len() as u64).to_be_bytes());
+    bytes.extend_from_slice(&json);
+}
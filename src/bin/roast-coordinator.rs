@@ -0,0 +1,339 @@
+//! roast-coordinator
+//!
+//! Serves the ROAST protocol to `roast-signer`s over a TCP or Unix socket.
+//!
+//! ```text
+//! roast-coordinator <group-config.json> <tcp:host:port | unix:path> [--state <state-file>]
+//! ```
+//!
+//! Messages to sign are read from stdin as one hex string per line. Each is announced to every
+//! connected signer, and its signature is written to stdout as a line of JSON once combined.
+//!
+//! Each connection is written to by its own thread, so a signer which stops reading can only ever
+//! fill its own queue of frames. Once that queue is full the signer is disconnected.
+use std::{
+    collections::HashMap,
+    env, fs,
+    io::{self, BufRead},
+    process,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        mpsc, Arc, Mutex,
+    },
+    thread,
+};
+
+use schnorr_fun::{
+    frost::{Frost, FrostKey},
+    nonce::Deterministic,
+    Message, Signature,
+};
+use secp256kfun::{
+    hex,
+    marker::{EvenY, Public},
+};
+use serde::Deserialize;
+use sha2::Sha256;
+
+use roast::{
+    coordinator::{Coordinator, RoastError, StoredMessage},
+    protocol::CoordinatorMessage,
+    storage::FileStore,
+    wire::{read_frame, write_frame, CoordinatorFrame, Endpoint, SignerFrame, Stream},
+};
+
+type RoastCoordinator = Coordinator<Frost<Sha256, Deterministic<Sha256>>, FrostKey<EvenY>>;
+
+/// The group the coordinator signs for
+#[derive(Deserialize)]
+struct GroupConfig {
+    joint_key: FrostKey<EvenY>,
+}
+
+/// How many frames may wait to be written to a signer before it is disconnected
+const OUTBOX_LEN: usize = 64;
+
+/// State shared between the connections
+struct Server {
+    coordinator: RoastCoordinator,
+    // the queue of frames to be written to each signer by its writer thread, with the id of the
+    // connection it writes to
+    connections: Mutex<HashMap<usize, (u64, mpsc::SyncSender<CoordinatorFrame>)>>,
+    next_connection: AtomicU64,
+    message: Mutex<Option<StoredMessage>>,
+    results: Mutex<mpsc::Sender<Result<Signature, RoastError>>>,
+}
+
+impl Server {
+    fn send(&self, index: usize, frame: &CoordinatorFrame) {
+        let mut connections = self.connections.lock().expect("got lock");
+        if let Some((_, outbox)) = connections.get(&index) {
+            if !queue(index, outbox, frame.clone()) {
+                connections.remove(&index);
+            }
+        }
+    }
+
+    /// Start signing a new message and announce it to every connected signer
+    fn start(&self, message: StoredMessage) -> Result<(), RoastError> {
+        // Holding the current message while the session starts keeps a signer connecting meanwhile
+        // from being told of the previous message, see `serve`
+        let mut current = self.message.lock().expect("got lock");
+        self.coordinator.start_session(message.clone())?;
+        self.announce(&mut current, message);
+        Ok(())
+    }
+
+    /// Make this the message being signed and announce it to every connected signer
+    fn announce(&self, current: &mut Option<StoredMessage>, message: StoredMessage) {
+        let frame = CoordinatorFrame::Sign {
+            message: message.clone(),
+        };
+        *current = Some(message);
+        self.connections
+            .lock()
+            .expect("got lock")
+            .retain(|index, (_, outbox)| queue(*index, outbox, frame.clone()));
+    }
+
+    fn serve(&self, mut stream: Stream) -> io::Result<()> {
+        let index = match read_frame(&mut stream)? {
+            SignerFrame::Hello { index } if index < self.coordinator.n_signers() => index,
+            frame => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("expected a hello from a signer, got {:?}", frame),
+                ))
+            }
+        };
+        eprintln!("signer {} connected", index);
+        let connection = self.next_connection.fetch_add(1, Ordering::Relaxed);
+        {
+            // Announce the message being signed before any protocol messages can be sent
+            let message = self.message.lock().expect("got lock");
+            let (outbox, frames) = mpsc::sync_channel(OUTBOX_LEN);
+            if let Some(message) = message.as_ref() {
+                let _ = outbox.send(CoordinatorFrame::Sign {
+                    message: message.clone(),
+                });
+            }
+            let writer = stream.try_clone()?;
+            thread::spawn(move || write_frames(index, writer, frames));
+            // A signer reconnecting replaces its previous connection, whose writer then closes it
+            self.connections
+                .lock()
+                .expect("got lock")
+                .insert(index, (connection, outbox));
+        }
+
+        let result = self.relay(index, &mut stream);
+        // Dropping the queue ends the writer thread, which closes the connection. A reconnect may
+        // already have replaced it, in which case the new connection is left alone.
+        let mut connections = self.connections.lock().expect("got lock");
+        if connections.get(&index).map(|(id, _)| *id) == Some(connection) {
+            connections.remove(&index);
+        }
+        result
+    }
+
+    /// Pass a signer's protocol messages to the coordinator, sending out its responses, until the
+    /// connection fails
+    fn relay(&self, index: usize, stream: &mut Stream) -> io::Result<()> {
+        loop {
+            let message = match read_frame(stream)? {
+                SignerFrame::Message(message) => message,
+                SignerFrame::Hello { .. } => continue,
+            };
+            // A late message for an earlier message is rejected as `RoastError::WrongMessage`
+            let response = match self.coordinator.receive(index, message) {
+                Ok(response) => response,
+                Err(e @ RoastError::TooFewHonest) => {
+                    let _ = self.results.lock().expect("got lock").send(Err(e));
+                    continue;
+                }
+                Err(e @ RoastError::InvalidSignature { .. }) => {
+                    eprintln!("{}, opening a new sign session", e);
+                    match self.coordinator.open_session() {
+                        Ok(Some(response)) => response,
+                        Ok(None) => continue,
+                        Err(e) => {
+                            let _ = self.results.lock().expect("got lock").send(Err(e));
+                            continue;
+                        }
+                    }
+                }
+                Err(e @ RoastError::UnexpectedSignatureShare(_)) => {
+                    // The signer is resending a share we have no record of asking for, so it
+                    // needs to start over with a fresh nonce
+                    eprintln!("rejected message from signer {}: {}", index, e);
+                    self.send(index, &CoordinatorFrame::Rejected);
+                    continue;
+                }
+                Err(e) => {
+                    eprintln!("rejected message from signer {}: {}", index, e);
+                    continue;
+                }
+            };
+            if let Some(message) = response.message {
+                if let CoordinatorMessage::Completed { signature } = &message {
+                    let _ = self
+                        .results
+                        .lock()
+                        .expect("got lock")
+                        .send(Ok(signature.clone()));
+                }
+                let frame = CoordinatorFrame::Message(message);
+                for recipient in response.recipients {
+                    self.send(recipient, &frame);
+                }
+            }
+        }
+    }
+}
+
+/// Queue a frame for a signer's writer thread
+///
+/// Returns whether the signer is still connected.
+fn queue(
+    index: usize,
+    outbox: &mpsc::SyncSender<CoordinatorFrame>,
+    frame: CoordinatorFrame,
+) -> bool {
+    match outbox.try_send(frame) {
+        Ok(()) => true,
+        Err(mpsc::TrySendError::Full(_)) => {
+            eprintln!(
+                "dropping signer {}: too many frames waiting to be sent",
+                index
+            );
+            false
+        }
+        // The writer thread has already reported why it stopped
+        Err(mpsc::TrySendError::Disconnected(_)) => false,
+    }
+}
+
+/// Write each queued frame to a signer until the signer is dropped or the connection fails
+///
+/// The connection is then closed, which also ends the thread reading from it.
+fn write_frames(index: usize, mut writer: Stream, frames: mpsc::Receiver<CoordinatorFrame>) {
+    for frame in frames {
+        if let Err(e) = write_frame(&mut writer, &frame) {
+            eprintln!("dropping signer {}: {}", index, e);
+            break;
+        }
+    }
+    let _ = writer.shutdown();
+}
+
+fn main() {
+    if let Err(e) = run() {
+        eprintln!("roast-coordinator: {}", e);
+        process::exit(1);
+    }
+}
+
+fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let (config_path, endpoint, state_path) =
+        match args.as_slice() {
+            [config, endpoint] => (config, endpoint, None),
+            [config, endpoint, flag, state] if flag == "--state" => (config, endpoint, Some(state)),
+            _ => return Err(
+                "usage: roast-coordinator <group-config.json> <endpoint> [--state <state-file>]"
+                    .into(),
+            ),
+        };
+    let config: GroupConfig = serde_json::from_slice(&fs::read(config_path)?)?;
+    let endpoint: Endpoint = endpoint.parse()?;
+
+    let frost = Frost::<Sha256, Deterministic<Sha256>>::default();
+    let public_key = config.joint_key.public_key();
+
+    let mut coordinator = Coordinator::new(frost.clone(), config.joint_key)
+        .with_observer(|event: &_| eprintln!("{:?}", event));
+    if let Some(state_path) = state_path {
+        coordinator = coordinator.with_store(FileStore::open(state_path)?)?;
+    }
+
+    let (results, signatures) = mpsc::channel();
+    let server = Arc::new(Server {
+        coordinator,
+        connections: Mutex::new(HashMap::new()),
+        next_connection: AtomicU64::new(0),
+        message: Mutex::new(None),
+        results: Mutex::new(results),
+    });
+
+    let listener = endpoint.bind()?;
+    eprintln!("listening on {}", endpoint);
+    {
+        let server = server.clone();
+        thread::spawn(move || loop {
+            match listener.accept() {
+                Ok(stream) => {
+                    let server = server.clone();
+                    thread::spawn(move || {
+                        if let Err(e) = server.serve(stream) {
+                            eprintln!("connection closed: {}", e);
+                        }
+                    });
+                }
+                Err(e) => eprintln!("failed to accept connection: {}", e),
+            }
+        });
+    }
+
+    // A coordinator restored midway through signing resumes that message first
+    let mut resumed = server.coordinator.message();
+    let mut lines = io::stdin().lock().lines();
+    loop {
+        let message = match resumed.take() {
+            Some(message) => match server.coordinator.result() {
+                // The message was signed before the restart
+                Some(signature) => {
+                    println!("{}", serde_json::to_string(&signature)?);
+                    continue;
+                }
+                // The coordinator is already signing it, so it only needs announcing
+                None => {
+                    server.announce(
+                        &mut server.message.lock().expect("got lock"),
+                        message.clone(),
+                    );
+                    message
+                }
+            },
+            None => {
+                let line = match lines.next() {
+                    Some(line) => line?,
+                    None => break,
+                };
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let bytes = hex::decode(line.trim())?;
+                let message = StoredMessage::from(Message::<Public>::raw(&bytes));
+                server.start(message.clone())?;
+                message
+            }
+        };
+
+        // Wait for a signature on this message, ignoring any late results of earlier messages
+        loop {
+            match signatures.recv()? {
+                Ok(signature)
+                    if frost
+                        .schnorr
+                        .verify(&public_key, message.as_message(), &signature) =>
+                {
+                    println!("{}", serde_json::to_string(&signature)?);
+                    break;
+                }
+                Ok(_) => continue,
+                Err(e) => return Err(e.into()),
+            }
+        }
+    }
+    Ok(())
+}
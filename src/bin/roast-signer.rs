@@ -0,0 +1,167 @@
+//! roast-signer
+//!
+//! Holds a secret share and answers a `roast-coordinator` over a TCP or Unix socket.
+//!
+//! ```text
+//! roast-signer <signer-config.json> <tcp:host:port | unix:path>
+//! ```
+//!
+//! The signer sends a nonce for every message the coordinator announces and signs every nonce set
+//! it is asked to sign in. Each signature the coordinator announces is written to stdout as a line
+//! of JSON. If the connection is lost the signer reconnects with exponential backoff.
+use std::{env, fs, io, process, thread, time::Duration};
+
+use schnorr_fun::{
+    frost::{Frost, FrostKey},
+    nonce::Deterministic,
+};
+use secp256kfun::{marker::EvenY, Scalar};
+use serde::Deserialize;
+use sha2::Sha256;
+
+use roast::{
+    protocol::{CoordinatorMessage, SignerMessage},
+    signer::RoastSigner,
+    wire::{read_frame, write_frame, CoordinatorFrame, Endpoint, SignerFrame, Stream},
+};
+
+type Signer = RoastSigner<Frost<Sha256, Deterministic<Sha256>>, FrostKey<EvenY>>;
+
+const INITIAL_BACKOFF: Duration = Duration::from_millis(100);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Our place in the group
+#[derive(Deserialize)]
+struct SignerConfig {
+    joint_key: FrostKey<EvenY>,
+    index: usize,
+    secret_share: Scalar,
+}
+
+/// The signing of the message most recently announced by the coordinator
+struct Signing {
+    signer: Signer,
+    /// Our last message to the coordinator, resent after reconnecting
+    last_message: Option<SignerMessage>,
+}
+
+struct Daemon {
+    frost: Frost<Sha256, Deterministic<Sha256>>,
+    config: SignerConfig,
+    signing: Option<Signing>,
+}
+
+impl Daemon {
+    /// Answer the coordinator until the connection is lost
+    fn serve(&mut self, stream: &mut Stream) -> io::Result<()> {
+        let mut rng = rand::thread_rng();
+        write_frame(
+            stream,
+            &SignerFrame::Hello {
+                index: self.config.index,
+            },
+        )?;
+
+        loop {
+            let reply = match read_frame(stream)? {
+                CoordinatorFrame::Sign { message } => match &mut self.signing {
+                    // The coordinator still has the message we were signing when the
+                    // connection was lost, so retransmit in case our last message was lost too
+                    Some(signing) if *signing.signer.message() == message => {
+                        signing.last_message.clone()
+                    }
+                    _ => {
+                        let (signer, nonce) = RoastSigner::new(
+                            &mut rng,
+                            self.frost.clone(),
+                            self.config.joint_key.clone(),
+                            self.config.index,
+                            self.config.secret_share.clone(),
+                            message.as_message(),
+                        );
+                        self.signing = Some(Signing {
+                            signer,
+                            last_message: None,
+                        });
+                        Some(nonce)
+                    }
+                },
+                CoordinatorFrame::Message(message) => {
+                    let signing = match &mut self.signing {
+                        Some(signing) => signing,
+                        None => continue,
+                    };
+                    let signed = signing.signer.signature().is_some();
+                    if message == CoordinatorMessage::Banned {
+                        eprintln!("banned by the coordinator");
+                    }
+                    let reply = match signing.signer.receive(&mut rng, message) {
+                        Ok(reply) => reply,
+                        Err(e) => {
+                            eprintln!("refusing to sign: {}", e);
+                            None
+                        }
+                    };
+                    // Only a completed signature which verifies is kept by the signer
+                    if let (false, Some(signature)) = (signed, signing.signer.signature()) {
+                        println!("{}", serde_json::to_string(&signature)?);
+                    }
+                    reply
+                }
+                // The coordinator has lost track of us, e.g. it restarted without its state, so
+                // rejoin with a fresh nonce
+                CoordinatorFrame::Rejected => {
+                    self.signing
+                        .as_mut()
+                        .map(|signing| SignerMessage::NonceOnly {
+                            message_id: signing.signer.message_id(),
+                            nonce: signing.signer.new_nonce(&mut rng),
+                        })
+                }
+            };
+
+            if let (Some(reply), Some(signing)) = (reply, &mut self.signing) {
+                signing.last_message = Some(reply.clone());
+                write_frame(stream, &SignerFrame::Message(reply))?;
+            }
+        }
+    }
+}
+
+fn main() {
+    if let Err(e) = run() {
+        eprintln!("roast-signer: {}", e);
+        process::exit(1);
+    }
+}
+
+fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let (config_path, endpoint) = match args.as_slice() {
+        [config, endpoint] => (config, endpoint),
+        _ => return Err("usage: roast-signer <signer-config.json> <endpoint>".into()),
+    };
+    let config: SignerConfig = serde_json::from_slice(&fs::read(config_path)?)?;
+    let endpoint: Endpoint = endpoint.parse()?;
+
+    let mut daemon = Daemon {
+        frost: Frost::default(),
+        config,
+        signing: None,
+    };
+    let mut backoff = INITIAL_BACKOFF;
+    loop {
+        match endpoint.connect() {
+            Ok(mut stream) => {
+                eprintln!("connected to {}", endpoint);
+                backoff = INITIAL_BACKOFF;
+                if let Err(e) = daemon.serve(&mut stream) {
+                    eprintln!("connection lost: {}", e);
+                }
+            }
+            Err(e) => eprintln!("failed to connect to {}: {}", endpoint, e),
+        }
+        thread::sleep(backoff);
+        backoff = (backoff * 2).min(MAX_BACKOFF);
+    }
+}
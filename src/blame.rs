@@ -0,0 +1,83 @@
+//! ROAST Blame Proofs
+//!
+//! When a signature share fails verification the coordinator marks its signer malicious. A
+//! [`BlameProof`] records everything needed to repeat that verification, so other signers and
+//! auditors can confirm the verdict with [`verify_blame_proof`] rather than trusting the
+//! coordinator.
+//!
+//! A proof shows that the share is invalid for the session, not that the accused signer sent it.
+//! Unless signer messages are authenticated, a dishonest coordinator could fabricate the share.
+use std::collections::HashSet;
+
+use schnorr_fun::musig::Nonce;
+use secp256kfun::{
+    marker::{Public, Zero},
+    Scalar,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{coordinator::StoredMessage, threshold_scheme::ThresholdScheme};
+
+/// Evidence that a signer sent an invalid signature share
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlameProof<K, N = Nonce, Z = Scalar<Public, Zero>, M = StoredMessage> {
+    /// The joint key being signed under
+    pub joint_key: K,
+    /// The message being signed
+    pub message: M,
+    /// The sign session the share was sent for
+    pub session_id: usize,
+    /// The nonce set of the sign session
+    pub nonce_set: Vec<(usize, N)>,
+    /// The accused signer
+    pub index: usize,
+    /// The share the accused signer sent
+    pub signature_share: Z,
+}
+
+/// The [`BlameProof`] of the threshold scheme `S`
+pub type SchemeBlameProof<S, K> = BlameProof<
+    K,
+    <S as ThresholdScheme<K>>::PublicNonce,
+    <S as ThresholdScheme<K>>::SignatureShare,
+    <S as ThresholdScheme<K>>::Message,
+>;
+
+/// Check a [`BlameProof`], confirming the accused signer's share fails verification
+///
+/// The caller must still check that `joint_key` is the group's key and that the message is one
+/// the group was asked to sign.
+///
+/// # Returns
+///
+/// Returns `true` if the share is invalid for the proof's sign session. A proof with a malformed
+/// nonce set, or one which does not include the accused signer, is rejected.
+pub fn verify_blame_proof<S: ThresholdScheme<K>, K: Clone>(
+    threshold_scheme: &S,
+    proof: &SchemeBlameProof<S, K>,
+) -> bool {
+    let n_signers = threshold_scheme.n_signers(&proof.joint_key);
+    let mut indices = HashSet::new();
+    let well_formed = proof
+        .nonce_set
+        .iter()
+        .all(|(index, _)| *index < n_signers && indices.insert(*index));
+    if !well_formed
+        || indices.len() < threshold_scheme.threshold(&proof.joint_key)
+        || !indices.contains(&proof.index)
+    {
+        return false;
+    }
+
+    let session = threshold_scheme.start_sign_session(
+        &proof.joint_key,
+        proof.nonce_set.clone(),
+        &proof.message,
+    );
+    !threshold_scheme.verify_signature_share(
+        &proof.joint_key,
+        &session,
+        proof.index,
+        proof.signature_share.clone(),
+    )
+}
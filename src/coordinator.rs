@@ -8,103 +8,699 @@
 //!
 //! The ROAST coordinator keeps track of responsive and malicious signers in order to work towards a
 //! complete and valid signature.
+//!
+//! A [`Coordinator`] is long-lived: call [`Coordinator::start_session`] for every message the group
+//! signs. Signers found to be malicious stay banned, and each signer's responsiveness history is
+//! kept across messages, while the nonces and sign sessions of each message are kept separate.
+//!
+//! The [`RoastState`] can be persisted to a [`StateStore`] so a restarted coordinator resumes exactly
+//! where it stopped, see [`Coordinator::with_store`].
 use std::{
     collections::{HashMap, HashSet},
-    fmt,
+    fmt, io,
     sync::{Arc, Mutex},
 };
 
 use secp256kfun::{
-    marker::{Public, Zero},
+    marker::{EvenY, Public, Zero},
     Scalar,
 };
 
-use schnorr_fun::{musig::Nonce, Message, Signature};
+use schnorr_fun::{
+    frost::{FrostKey, SignSession},
+    musig::Nonce,
+    Message, Signature,
+};
+
+use serde::{Deserialize, Serialize};
 
-use crate::threshold_scheme::ThresholdScheme;
+use crate::{
+    announcement::{AnnouncementScheme, SchnorrAnnouncements, SessionAnnouncement},
+    blame::{BlameProof, SchemeBlameProof},
+    observer::{BanReason, CoordinatorEvent, CoordinatorObserver},
+    protocol::{
+        message_id, CoordinatorMessage, SchemeCoordinatorMessage, SchemeSignerMessage,
+        SignerMessage,
+    },
+    selection::{FirstResponsive, SignerSelection},
+    storage::StateStore,
+    threshold_scheme::ThresholdScheme,
+};
 
-// TODO: we may want to continue the roast coordinator state to the next message signing session
-// such that we keep our list of malicious or responsive signers. fn start_session() & Option<Message>?
-pub struct Coordinator<'a, S, K> {
+pub struct Coordinator<S: ThresholdScheme<K>, K, T: AnnouncementScheme = SchnorrAnnouncements> {
     pub threshold_scheme: S,
     pub joint_key: K,
     n_signers: usize,
     threshold: usize,
-    state: Arc<Mutex<RoastState<'a>>>,
+    state: Arc<Mutex<SchemeState<S, K>>>,
+    observers: Vec<Box<dyn CoordinatorObserver<S::Signature> + Send + Sync>>,
+    store: Option<Mutex<BoxedStore<S, K>>>,
+    announcement_scheme: T,
+    announcement_key: Option<T::AnnouncementKey>,
+    selection: Mutex<Box<dyn SignerSelection + Send>>,
+    optimistic: bool,
 }
 
-#[derive(Debug)]
-pub struct RoastState<'a> {
-    message: Message<'a, Public>,
-    responsive_signers: HashSet<usize>,
+/// Coordinator state which is carried across every message signed by the group
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RoastState<
+    N = Nonce,
+    Z = Scalar<Public, Zero>,
+    Sig = Signature,
+    M = StoredMessage,
+    P = SignSession,
+    K = FrostKey<EvenY>,
+> {
+    // states persisted before the group was recorded are assumed to belong to it
+    #[serde(default = "Option::default")]
+    group: Option<Group<K>>,
     malicious_signers: HashSet<usize>,
+    signer_records: HashMap<usize, SignerRecord>,
+    #[serde(default = "HashMap::new")]
+    invalid_shares: HashMap<usize, InvalidShare<N, Z, M>>,
+    // the prepared sign sessions are never persisted
+    #[serde(bound(
+        serialize = "N: Serialize, Z: Serialize, Sig: Serialize, M: Serialize",
+        deserialize = "N: Deserialize<'de>, Z: Deserialize<'de>, Sig: Deserialize<'de>, \
+                       M: Deserialize<'de>"
+    ))]
+    message_state: Option<MessageState<N, Z, Sig, M, P>>,
+}
+
+/// The [`RoastState`] of a coordinator using the threshold scheme `S`
+pub type SchemeState<S, K> = RoastState<
+    <S as ThresholdScheme<K>>::PublicNonce,
+    <S as ThresholdScheme<K>>::SignatureShare,
+    <S as ThresholdScheme<K>>::Signature,
+    <S as ThresholdScheme<K>>::Message,
+    <S as ThresholdScheme<K>>::SignSession,
+    K,
+>;
+
+type SchemeMessageState<S, K> = MessageState<
+    <S as ThresholdScheme<K>>::PublicNonce,
+    <S as ThresholdScheme<K>>::SignatureShare,
+    <S as ThresholdScheme<K>>::Signature,
+    <S as ThresholdScheme<K>>::Message,
+    <S as ThresholdScheme<K>>::SignSession,
+>;
+
+type SchemeInvalidShare<S, K> = InvalidShare<
+    <S as ThresholdScheme<K>>::PublicNonce,
+    <S as ThresholdScheme<K>>::SignatureShare,
+    <S as ThresholdScheme<K>>::Message,
+>;
+
+/// The [`RoastResponse`] of a coordinator using the threshold scheme `S` and announcement scheme
+/// `T`
+pub type SchemeResponse<S, K, T = SchnorrAnnouncements> = RoastResponse<
+    <S as ThresholdScheme<K>>::PublicNonce,
+    <S as ThresholdScheme<K>>::Signature,
+    <T as AnnouncementScheme>::AnnouncementSignature,
+>;
+
+type BoxedStore<S, K> = Box<dyn StateStore<SchemeState<S, K>> + Send>;
+
+type OpenedSession<S, K, T> = Result<Option<SchemeResponse<S, K, T>>, SchemeError<S, K>>;
+
+/// The group whose signing a [`RoastState`] records
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Group<K> {
+    joint_key: K,
+    threshold: usize,
+    n_signers: usize,
+}
+
+/// The evidence behind marking a signer malicious for an invalid signature share
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct InvalidShare<N, Z, M> {
+    message: M,
+    session_id: usize,
+    nonce_set: Vec<(usize, N)>,
+    signature_share: Z,
+}
+
+/// The state of signing a single message
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MessageState<N, Z, Sig, M, P> {
+    message: M,
+    responsive_signers: HashSet<usize>,
     session_counter: usize,
-    latest_nonces: HashMap<usize, Nonce>,
-    sessions: HashMap<usize, Arc<Mutex<RoastSignSession>>>,
+    latest_nonces: HashMap<usize, N>,
+    #[serde(bound(
+        serialize = "N: Serialize, Z: Serialize",
+        deserialize = "N: Deserialize<'de>, Z: Deserialize<'de>"
+    ))]
+    sessions: HashMap<usize, RoastSignSession<N, Z, P>>,
     signer_session_map: HashMap<usize, usize>,
+    // the signature, once the message is signed
+    #[serde(default = "Option::default")]
+    signature: Option<Sig>,
+}
+
+/// A message being signed, owned so that it can be persisted with the coordinator state
+///
+/// The message is kept in the form it is hashed in. A [`Message::plain`] hashes its app tag padded
+/// to 64 bytes followed by its bytes, so it is hashed identically to a [`Message::raw`] over that
+/// concatenation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoredMessage(Vec<u8>);
+
+impl StoredMessage {
+    /// The message as it is passed to the threshold scheme
+    pub fn as_message(&self) -> Message<'_, Public> {
+        Message::raw(&self.0)
+    }
+
+    /// The message in the form it is hashed in
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl From<Message<'_, Public>> for StoredMessage {
+    fn from(message: Message<'_, Public>) -> Self {
+        let mut bytes = vec![];
+        if let Some(app_tag) = message.app_tag {
+            let mut padded_tag = [0u8; 64];
+            padded_tag[..app_tag.len()].copy_from_slice(app_tag.as_bytes());
+            bytes.extend_from_slice(&padded_tag);
+        }
+        bytes.extend_from_slice(<&[u8]>::from(message.bytes));
+        StoredMessage(bytes)
+    }
 }
 
-#[derive(Debug)]
-pub struct RoastSignSession {
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RoastSignSession<N, Z, P> {
     pub signers: HashSet<usize>,
-    nonces: Vec<(usize, Nonce)>,
-    sig_shares: Vec<Scalar<Public, Zero>>,
+    nonces: Vec<(usize, N)>,
+    sig_shares: HashMap<usize, Z>,
+    // shares stored without verification, with the position they arrived at
+    #[serde(default = "HashMap::new")]
+    unverified: HashMap<usize, usize>,
+    // whether the shares combined into a signature which failed verification
+    #[serde(default)]
+    failed: bool,
+    #[serde(skip, default = "Prepared::default")]
+    prepared: Prepared<P>,
+}
+
+/// The threshold scheme's sign session, prepared when the first signature share arrives
+///
+/// It is derived from the nonce set and message, so it is not persisted but prepared again after
+/// the state is restored, and is ignored when comparing states.
+#[derive(Debug, Clone)]
+struct Prepared<P>(Option<P>);
+
+impl<P> Default for Prepared<P> {
+    fn default() -> Self {
+        Self(None)
+    }
+}
+
+impl<P> PartialEq for Prepared<P> {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+/// The responsiveness history of a signer across all messages
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignerRecord {
+    /// Number of times the signer has been marked responsive
+    pub times_responsive: usize,
+    /// Number of sign sessions the signer has been asked to sign in
+    pub sessions_joined: usize,
+    /// Number of valid signature shares the signer has provided
+    pub shares_accepted: usize,
+    /// Sum of the positions, counting from zero, at which the signer's accepted shares arrived
+    /// within their sign sessions
+    #[serde(default)]
+    pub share_positions: usize,
+}
+
+impl SignerRecord {
+    /// The average position at which the signer's accepted shares arrived, if any were accepted
+    pub fn average_share_position(&self) -> Option<f64> {
+        if self.shares_accepted == 0 {
+            return None;
+        }
+        Some(self.share_positions as f64 / self.shares_accepted as f64)
+    }
+
+    /// The fraction of joined sign sessions the signer provided a valid share for, if it has
+    /// joined any
+    pub fn completion_rate(&self) -> Option<f64> {
+        if self.sessions_joined == 0 {
+            return None;
+        }
+        Some(self.shares_accepted as f64 / self.sessions_joined as f64)
+    }
 }
 
-#[derive(Debug)]
-pub struct RoastResponse {
+/// The coordinator's reply to a signer message
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RoastResponse<N = Nonce, Sig = Signature, A = Signature> {
+    /// The signers the message should be delivered to
     pub recipients: Vec<usize>,
-    pub combined_signature: Option<Signature>,
-    pub nonce_set: Option<Vec<(usize, Nonce)>>,
+    /// The message to deliver, if there is anything to send
+    pub message: Option<CoordinatorMessage<N, Sig, A>>,
 }
 
-#[derive(Debug, Clone)]
-pub enum RoastError {
+/// An error of a [`Coordinator`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum RoastError<N = Nonce, Z = Scalar<Public, Zero>> {
+    /// More signers have been marked malicious than the threshold can tolerate
     TooFewHonest,
+    /// [`Coordinator::start_session`] has not been called with a message to sign
+    NoActiveSession,
+    /// A message claimed to be from a signer index outside the group
+    UnknownSigner(usize),
+    /// A signer sent a message for another message than the one being signed, e.g. one delayed
+    /// from before [`Coordinator::start_session`] was called again
+    WrongMessage(usize),
+    /// A signer who was asked to sign replied without a signature share
+    MissingSignatureShare(usize),
+    /// A signer sent a signature share for a sign session that was never opened
+    UnknownSession { index: usize, session_id: usize },
+    /// A signer sent a signature share for an earlier sign session which it no longer owes a
+    /// share for, e.g. a delayed share for a session it has already answered
+    StaleSession { index: usize, session_id: usize },
+    /// A signer sent a signature share without having been asked to sign
+    UnexpectedSignatureShare(usize),
+    /// The state could not be written to the [`StateStore`], so the message was not processed
+    Storage(String),
+    /// The signature shares of a sign session, though each was accepted, combined into a signature
+    /// which fails verification under the joint key. The session is closed as failed.
+    InvalidSignature {
+        session_id: usize,
+        nonce_set: Vec<(usize, N)>,
+        signature_shares: Vec<(usize, Z)>,
+    },
 }
 
-impl fmt::Display for RoastError {
+/// The [`RoastError`] of a coordinator using the threshold scheme `S`
+pub type SchemeError<S, K> =
+    RoastError<<S as ThresholdScheme<K>>::PublicNonce, <S as ThresholdScheme<K>>::SignatureShare>;
+
+impl<N, Z> fmt::Display for RoastError<N, Z> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Self::TooFewHonest => write!(f, "Too few honest signers"),
+            Self::NoActiveSession => write!(f, "No message is being signed"),
+            Self::UnknownSigner(index) => write!(f, "Unknown signer index {}", index),
+            Self::WrongMessage(index) => write!(
+                f,
+                "Signer {} sent a message for another message than the one being signed",
+                index
+            ),
+            Self::MissingSignatureShare(index) => {
+                write!(f, "Signer {} did not provide a signature share", index)
+            }
+            Self::UnknownSession { index, session_id } => write!(
+                f,
+                "Signer {} sent a signature share for unknown sign session {}",
+                index, session_id
+            ),
+            Self::StaleSession { index, session_id } => write!(
+                f,
+                "Signer {} sent a signature share for stale sign session {}",
+                index, session_id
+            ),
+            Self::UnexpectedSignatureShare(index) => write!(
+                f,
+                "Signer {} sent a signature share without being asked to sign",
+                index
+            ),
+            Self::Storage(error) => write!(f, "Failed to persist coordinator state: {}", error),
+            Self::InvalidSignature { session_id, .. } => write!(
+                f,
+                "Sign session {} combined a signature which fails verification",
+                session_id
+            ),
+        }
+    }
+}
+
+impl<N: fmt::Debug, Z: fmt::Debug> std::error::Error for RoastError<N, Z> {}
+
+impl<N: Clone + PartialEq, Z: Clone + PartialEq, Sig, M, P> MessageState<N, Z, Sig, M, P> {
+    /// Whether a signer's message has already been processed, as its nonce is recorded as the
+    /// signer's and any share it carries was taken as the signer's share
+    ///
+    /// Only persisted state is consulted, so duplicates are still recognised after a restore.
+    fn records(&self, index: usize, message: &SignerMessage<N, Z>) -> bool {
+        let nonce = message.nonce();
+        let nonce_recorded = self.latest_nonces.get(&index) == Some(&nonce)
+            || self.sessions.values().any(|session| {
+                session
+                    .nonces
+                    .iter()
+                    .any(|(i, session_nonce)| *i == index && *session_nonce == nonce)
+            });
+        let share_recorded = match message {
+            SignerMessage::NonceOnly { .. } => true,
+            SignerMessage::ShareAndNonce {
+                session_id,
+                signature_share,
+                ..
+            } => {
+                self.sessions
+                    .get(session_id)
+                    .and_then(|session| session.sig_shares.get(&index))
+                    == Some(signature_share)
+            }
+        };
+        nonce_recorded && share_recorded
+    }
+}
+
+impl<N, Z, Sig, M, P> MessageState<N, Z, Sig, M, P> {
+    /// The sign session the signer has been asked to sign in and has not yet replied to
+    fn owed_session(&self, index: usize) -> Option<usize> {
+        let session_id = self.signer_session_map.get(&index).copied()?;
+        let session = self.sessions.get(&session_id)?;
+        (!session.failed && !session.sig_shares.contains_key(&index)).then_some(session_id)
+    }
+
+    fn new(message: M, session_counter: usize) -> Self {
+        Self {
+            message,
+            responsive_signers: HashSet::new(),
+            latest_nonces: HashMap::new(),
+            sessions: HashMap::new(),
+            signer_session_map: HashMap::new(),
+            session_counter,
+            signature: None,
         }
     }
 }
 
-// impl<'a, H: Digest + Clone + Digest<OutputSize = U32>, NG> Coordinator<'a, H, NG> {
-impl<'a, S: ThresholdScheme<K>, K: Clone> Coordinator<'a, S, K> {
+impl<S: ThresholdScheme<K>, K: Clone> Coordinator<S, K> {
     /// Create a new ROAST [`Coordinator`] to receive signatures and nonces from signers
     ///
+    /// The threshold and number of signers are those of the joint key. Call
+    /// [`Coordinator::start_session`] with a message before receiving from signers.
+    ///
     /// # Returns
     ///
     /// Returns a Coordinator with a fresh state
-    pub fn new(
+    pub fn new(threshold_scheme: S, joint_key: K) -> Self {
+        Self::with_announcement_scheme(threshold_scheme, joint_key, SchnorrAnnouncements)
+    }
+}
+
+impl<S: ThresholdScheme<K>, K: Clone, T: AnnouncementScheme> Coordinator<S, K, T> {
+    /// Create a new ROAST [`Coordinator`] which announces sign sessions with `announcement_scheme`
+    ///
+    /// Announcements are only signed once a key is given with
+    /// [`Coordinator::with_announcement_key`].
+    pub fn with_announcement_scheme(
         threshold_scheme: S,
         joint_key: K,
-        message: Message<'a, Public>,
-        threshold: usize,
-        n_signers: usize,
+        announcement_scheme: T,
     ) -> Self {
-        return Self {
+        let threshold = threshold_scheme.threshold(&joint_key);
+        let n_signers = threshold_scheme.n_signers(&joint_key);
+        Self {
             threshold_scheme,
-            joint_key,
+            joint_key: joint_key.clone(),
             n_signers,
             threshold,
             state: Arc::new(Mutex::new(RoastState {
-                message,
-                responsive_signers: HashSet::new(),
+                group: Some(Group {
+                    joint_key,
+                    threshold,
+                    n_signers,
+                }),
                 malicious_signers: HashSet::new(),
-                latest_nonces: HashMap::new(),
-                sessions: HashMap::new(),
-                signer_session_map: HashMap::new(),
-                session_counter: 0,
+                signer_records: HashMap::new(),
+                invalid_shares: HashMap::new(),
+                message_state: None,
             })),
+            observers: vec![],
+            store: None,
+            announcement_scheme,
+            announcement_key: None,
+            selection: Mutex::new(Box::new(FirstResponsive)),
+            optimistic: false,
+        }
+    }
+
+    /// Persist the coordinator state to a [`StateStore`]
+    ///
+    /// If the store already holds a state, e.g. one written before a crash, the coordinator resumes
+    /// from it: banned signers, signer records, the message being signed and its open sign sessions
+    /// are all restored. Otherwise the current state is written to the store.
+    ///
+    /// From then on the state is written to the store before [`Coordinator::start_session`] or
+    /// [`Coordinator::receive`] return, so a response is never sent for a state that was not
+    /// persisted.
+    ///
+    /// # Returns
+    ///
+    /// Returns an [`io::ErrorKind::InvalidData`] error if the stored state belongs to a group with a
+    /// different joint key, threshold or number of signers.
+    pub fn with_store(
+        mut self,
+        mut store: impl StateStore<SchemeState<S, K>> + Send + 'static,
+    ) -> io::Result<Self>
+    where
+        K: PartialEq,
+    {
+        let mut guard = self.state.lock().expect("got lock");
+        match store.load()? {
+            Some(mut state) => {
+                match (&state.group, &guard.group) {
+                    (Some(stored), Some(group)) if stored != group => {
+                        return Err(io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            "stored coordinator state belongs to a different group",
+                        ));
+                    }
+                    _ => {}
+                }
+                state.group = guard.group.take();
+                *guard = state;
+            }
+            None => store.save(&guard)?,
+        }
+        drop(guard);
+        self.store = Some(Mutex::new(Box::new(store)));
+        Ok(self)
+    }
+
+    /// The number of signature shares needed to sign
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    /// The number of signers in the group
+    pub fn n_signers(&self) -> usize {
+        self.n_signers
+    }
+
+    /// The message currently being signed, if any
+    pub fn message(&self) -> Option<S::Message> {
+        self.state
+            .lock()
+            .expect("got lock")
+            .message_state
+            .as_ref()
+            .map(|message_state| message_state.message.clone())
+    }
+
+    /// The signature of the current message, once it has been signed
+    pub fn result(&self) -> Option<S::Signature> {
+        self.state
+            .lock()
+            .expect("got lock")
+            .message_state
+            .as_ref()
+            .and_then(|message_state| message_state.signature.clone())
+    }
+
+    /// A snapshot of the coordinator state
+    pub fn state(&self) -> SchemeState<S, K> {
+        self.state.lock().expect("got lock").clone()
+    }
+
+    /// Write the state to the store, rolling back to `previous` if that fails
+    fn persist(
+        &self,
+        state: &mut SchemeState<S, K>,
+        previous: Option<SchemeState<S, K>>,
+    ) -> Result<(), SchemeError<S, K>> {
+        let store = match &self.store {
+            Some(store) => store,
+            None => return Ok(()),
         };
+        store.lock().expect("got lock").save(state).map_err(|e| {
+            if let Some(previous) = previous {
+                *state = previous;
+            }
+            RoastError::Storage(e.to_string())
+        })
+    }
+
+    /// Sign the nonce set of every sign request with this key
+    ///
+    /// Signers holding the public key can then detect and prove equivocation, see
+    /// [`crate::announcement`].
+    pub fn with_announcement_key(mut self, announcement_key: T::AnnouncementKey) -> Self {
+        self.announcement_key = Some(announcement_key);
+        self
+    }
+
+    /// Choose the signers of each sign session with a [`SignerSelection`] strategy
+    ///
+    /// By default a session is opened with the first `threshold` responsive signers.
+    pub fn with_selection(mut self, selection: impl SignerSelection + Send + 'static) -> Self {
+        self.selection = Mutex::new(Box::new(selection));
+        self
+    }
+
+    /// Only verify the combined signature, rather than every signature share as it arrives
+    ///
+    /// Shares are stored unchecked until a sign session has `threshold` of them. If the combined
+    /// signature fails verification, each of the session's shares is then verified to find and ban
+    /// the signers responsible, and the session is left incomplete. A signer whose share is still
+    /// unchecked has it checked before being asked to sign again, so invalid shares cost no more
+    /// sign sessions than when every share is checked.
+    pub fn with_optimistic_verification(mut self) -> Self {
+        self.optimistic = true;
+        self
+    }
+
+    /// Add an observer to be notified of every [`CoordinatorEvent`]
+    pub fn with_observer(
+        mut self,
+        observer: impl CoordinatorObserver<S::Signature> + Send + Sync + 'static,
+    ) -> Self {
+        self.observers.push(Box::new(observer));
+        self
+    }
+
+    fn emit(&self, event: CoordinatorEvent<S::Signature>) {
+        for observer in &self.observers {
+            observer.on_event(&event);
+        }
+    }
+
+    /// Begin a signing session for a new message
+    ///
+    /// Any unfinished signing of a previous message is abandoned, along with its nonces and sign
+    /// sessions. Malicious signers remain banned and signer records are kept. Session ids carry on
+    /// from those of the previous message.
+    ///
+    /// # Returns
+    ///
+    /// Returns a [`RoastError::TooFewHonest`] if previous messages have already revealed too many
+    /// malicious signers for this message to ever be signed.
+    pub fn start_session(&self, message: impl Into<S::Message>) -> Result<(), SchemeError<S, K>> {
+        let mut roast_state = self.state.lock().expect("got lock");
+        if roast_state.malicious_signers.len() > self.n_signers - self.threshold {
+            return Err(RoastError::TooFewHonest);
+        }
+        let previous = self.store.as_ref().map(|_| roast_state.clone());
+        // Session ids keep counting across messages, so a session announced while signing a
+        // message before is never mistaken for equivocation if that message is signed again
+        let session_counter = roast_state
+            .message_state
+            .as_ref()
+            .map_or(0, |message_state| message_state.session_counter);
+        roast_state.message_state = Some(MessageState::new(message.into(), session_counter));
+        self.persist(&mut roast_state, previous)?;
+        self.emit(CoordinatorEvent::SessionStarted);
+        Ok(())
+    }
+
+    /// The signers that have been marked malicious, across all messages
+    pub fn malicious_signers(&self) -> HashSet<usize> {
+        self.state
+            .lock()
+            .expect("got lock")
+            .malicious_signers
+            .clone()
+    }
+
+    /// Proof that a signer was marked malicious for sending an invalid signature share
+    ///
+    /// See [`verify_blame_proof`](crate::blame::verify_blame_proof).
+    pub fn blame_proof(&self, index: usize) -> Option<SchemeBlameProof<S, K>> {
+        self.state
+            .lock()
+            .expect("got lock")
+            .invalid_shares
+            .get(&index)
+            .map(|invalid_share| BlameProof {
+                joint_key: self.joint_key.clone(),
+                message: invalid_share.message.clone(),
+                session_id: invalid_share.session_id,
+                nonce_set: invalid_share.nonce_set.clone(),
+                index,
+                signature_share: invalid_share.signature_share.clone(),
+            })
+    }
+
+    /// The responsiveness history of a signer, across all messages
+    pub fn signer_record(&self, index: usize) -> SignerRecord {
+        self.state
+            .lock()
+            .expect("got lock")
+            .signer_records
+            .get(&index)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Open a sign session without waiting for the [`SignerSelection`]'s full pool
+    ///
+    /// Call this on a timer when using a pool larger than the threshold, so that unresponsive
+    /// signers cannot stall signing.
+    ///
+    /// # Returns
+    ///
+    /// Returns the sign request to send if at least `threshold` signers are responsive and the
+    /// message has not yet been signed.
+    pub fn open_session(&self) -> OpenedSession<S, K, T> {
+        let mut guard = self.state.lock().expect("got lock");
+        let previous = self.store.as_ref().map(|_| guard.clone());
+        let RoastState {
+            malicious_signers,
+            signer_records,
+            invalid_shares,
+            message_state,
+            ..
+        } = &mut *guard;
+        let message_state = message_state.as_mut().ok_or(RoastError::NoActiveSession)?;
+        if message_state.signature.is_some()
+            || message_state.responsive_signers.len() < self.threshold
+        {
+            return Ok(None);
+        }
+        if let Err(e) = self.verify_pending_shares(
+            message_state,
+            malicious_signers,
+            invalid_shares,
+            signer_records,
+        ) {
+            self.persist(&mut guard, previous)?;
+            return Err(e);
+        }
+        if message_state.responsive_signers.len() < self.threshold {
+            self.persist(&mut guard, previous)?;
+            return Ok(None);
+        }
+        let response = self.open_sign_session(message_state, signer_records);
+        self.persist(&mut guard, previous)?;
+        Ok(Some(response))
     }
 
     /// Receive a signature share and new nonce from a signer
     ///
-    /// For the first signing session, signers must first send just a nonce with None signature.
+    /// For the first signing session, signers must first send a [`SignerMessage::NonceOnly`].
     ///
     /// This function contains the core of *[ROAST paper's coordinator algorithm]* (Figure 4).
     /// Hopefully the comments are helpful in comparison.
@@ -113,179 +709,579 @@ impl<'a, S: ThresholdScheme<K>, K: Clone> Coordinator<'a, S, K> {
     ///
     /// # Returns
     ///
-    /// Returns a [`RoastResponse`] which contains an optional signature and nonce set.
+    /// Returns a [`RoastResponse`] which contains an optional [`CoordinatorMessage`].
     /// Check the `recipients` field to determine who this message should be broadcast too.
+    ///
+    /// Malformed messages are rejected with a [`RoastError`] and leave the state untouched, as are
+    /// messages tagged with the [`message_id`] of another message.
+    ///
+    /// A combined signature is only returned once it verifies under the joint key. If it does not,
+    /// and no signature share is found to blame, a [`RoastError::InvalidSignature`] is returned
+    /// and the sign session is closed as failed. The signer is still marked responsive, so call
+    /// [`Coordinator::open_session`] to carry on signing.
+    ///
+    /// A retransmission of a signer's previous message is answered with the previous response,
+    /// addressed only to that signer, so an at-least-once transport never causes a signer to be
+    /// marked malicious. A duplicate of an earlier message, delivered after a later one, is
+    /// recognised from the nonce and share it carries and ignored.
+    ///
+    /// Once the message is signed, the [`CoordinatorMessage::Completed`] signature is sent to every
+    /// signer, telling those still in other sign sessions to discard their nonces. Any further
+    /// message is answered with the signature, see also [`Coordinator::result`].
     pub fn receive(
         &self,
         index: usize,
-        signature_share: Option<Scalar<Public, Zero>>,
-        new_nonce: Nonce,
-    ) -> Result<RoastResponse, RoastError> {
-        let mut roast_state = self.state.lock().expect("got lock");
-        // dbg!(&roast_state);
+        message: SchemeSignerMessage<S, K>,
+    ) -> Result<SchemeResponse<S, K, T>, SchemeError<S, K>> {
+        let mut guard = self.state.lock().expect("got lock");
+        let banned = guard.malicious_signers.contains(&index);
+        let message_state = guard
+            .message_state
+            .as_mut()
+            .ok_or(RoastError::NoActiveSession)?;
+
+        // A message sent while signing another message has no place in this one
+        if message.message_id() != message_id(&message_state.message) {
+            return Err(RoastError::WrongMessage(index));
+        }
+
+        // Once signed, every signer is answered with the signature
+        if let Some(signature) = &message_state.signature {
+            if index < self.n_signers {
+                return Ok(RoastResponse {
+                    recipients: vec![index],
+                    message: Some(CoordinatorMessage::Completed {
+                        signature: signature.clone(),
+                    }),
+                });
+            }
+        }
 
-        if roast_state.malicious_signers.contains(&index) {
-            println!("Malicious signer tried to send signature! {}", index);
+        // A banned signer is told so, whatever it sends
+        if !banned && message_state.records(index, &message) {
+            // A retransmission of the signer's latest message is answered again, in case our
+            // reply was lost, with the sign request it is still owing a share for
+            if message_state.latest_nonces.get(&index) == Some(&message.nonce()) {
+                self.emit(CoordinatorEvent::RetransmissionReplayed { index });
+                let sign_request = message_state.owed_session(index).map(|session_id| {
+                    let nonce_set = message_state.sessions[&session_id].nonces.clone();
+                    self.sign_request(&message_state.message, session_id, nonce_set)
+                });
+                return Ok(RoastResponse {
+                    recipients: vec![index],
+                    message: sign_request,
+                });
+            }
+            self.emit(CoordinatorEvent::DuplicateIgnored { index });
             return Ok(RoastResponse {
                 recipients: vec![index],
-                combined_signature: None,
-                nonce_set: None,
+                message: None,
             });
         }
 
-        if roast_state.responsive_signers.contains(&index) {
-            println!(
-                "Unsolicited reply from signer {}, marking malicious.",
-                index
-            );
+        let previous = self.store.as_ref().map(|_| guard.clone());
+        let response = match self.process(&mut guard, index, message) {
+            Ok(response) => response,
+            Err(e) => {
+                // Signers may have been banned before the error was found
+                self.persist(&mut guard, previous)?;
+                return Err(e);
+            }
+        };
+        self.persist(&mut guard, previous)?;
+        Ok(response)
+    }
 
-            // Mark malicious
-            roast_state.malicious_signers.insert(index);
-            if roast_state.malicious_signers.len() > self.n_signers - self.threshold {
-                return Err(RoastError::TooFewHonest);
+    fn process(
+        &self,
+        roast_state: &mut SchemeState<S, K>,
+        index: usize,
+        message: SchemeSignerMessage<S, K>,
+    ) -> Result<SchemeResponse<S, K, T>, SchemeError<S, K>> {
+        let signature_share = message.signature_share();
+        let new_nonce = message.nonce();
+        let RoastState {
+            malicious_signers,
+            signer_records,
+            invalid_shares,
+            message_state,
+            ..
+        } = roast_state;
+        let message_state = message_state.as_mut().ok_or(RoastError::NoActiveSession)?;
+
+        if index >= self.n_signers {
+            return Err(RoastError::UnknownSigner(index));
+        }
+
+        if malicious_signers.contains(&index) {
+            self.emit(CoordinatorEvent::BannedSignerIgnored { index });
+            return Ok(RoastResponse {
+                recipients: vec![index],
+                message: Some(CoordinatorMessage::Banned),
+            });
+        }
+
+        // The sign session S_i has been asked to sign in and has not yet replied to
+        let latest_session = message_state.signer_session_map.get(&index).copied();
+        let owed_session = message_state.owed_session(index);
+
+        match message {
+            SignerMessage::ShareAndNonce { session_id, .. } if owed_session != Some(session_id) => {
+                return Err(match latest_session {
+                    None => RoastError::UnexpectedSignatureShare(index),
+                    // A session the signer was never asked to sign in is unknown to it
+                    Some(_)
+                        if !message_state
+                            .sessions
+                            .get(&session_id)
+                            .map(|session| session.signers.contains(&index))
+                            .unwrap_or(false) =>
+                    {
+                        RoastError::UnknownSession { index, session_id }
+                    }
+                    Some(_) => RoastError::StaleSession { index, session_id },
+                });
             }
+            SignerMessage::NonceOnly { .. } if owed_session.is_some() => {
+                return Err(RoastError::MissingSignatureShare(index));
+            }
+            _ => {}
+        }
 
+        if message_state.responsive_signers.contains(&index) {
+            // Mark malicious
+            self.ban(malicious_signers, index, BanReason::UnsolicitedMessage)?;
+            return Ok(RoastResponse {
+                recipients: vec![index],
+                message: Some(CoordinatorMessage::Banned),
+            });
+        }
+
+        // Honest signers refuse to sign a nonce set reusing a nonce they have signed with before,
+        // so a signer doing so could stall every session it joins. Every nonce handed out for
+        // this message is checked, whether still waiting for a session or in any session opened,
+        // failed ones included.
+        let reused_nonce = message_state
+            .latest_nonces
+            .values()
+            .chain(
+                message_state
+                    .sessions
+                    .values()
+                    .flat_map(|session| session.nonces.iter().map(|(_, nonce)| nonce)),
+            )
+            .any(|nonce| *nonce == new_nonce);
+        if reused_nonce {
+            self.ban(malicious_signers, index, BanReason::ReusedNonce)?;
             return Ok(RoastResponse {
                 recipients: vec![index],
-                combined_signature: None,
-                nonce_set: None,
+                message: Some(CoordinatorMessage::Banned),
             });
         }
 
+        let mut failure = None;
         // If this is not the inital message from S_i
-        match roast_state.signer_session_map.get(&index) {
-            Some(session_id) => {
-                println!(
-                    "Party {} sent a signature for sign session {}",
-                    index, session_id
-                );
-                let nonces = {
-                    let roast_session = roast_state
-                        .sessions
-                        .get(&session_id)
-                        .unwrap()
-                        .lock()
-                        .expect("got lock");
-                    roast_session.nonces.clone()
-                };
-                if !self.threshold_scheme.verify_signature_share(
-                    self.joint_key.clone(),
-                    nonces.clone(),
+        if let (Some(session_id), Some(signature_share)) = (owed_session, signature_share) {
+            // Store the signature share, unchecked until verified below
+            let roast_session = message_state
+                .sessions
+                .get_mut(&session_id)
+                .expect("owed session is open");
+            let position = roast_session.sig_shares.len();
+            roast_session.unverified.insert(index, position);
+            roast_session.sig_shares.insert(index, signature_share);
+
+            // Optimistically, the share is only checked once the session's shares are combined
+            if !self.optimistic
+                && !self.check_share(
+                    message_state,
+                    session_id,
                     index,
-                    signature_share.expect(
-                        "party unexpectedly provided None signature share for a sign session",
-                    ),
-                    roast_state.message,
-                ) {
-                    println!("Invalid signature, marking {} malicious.", index);
-                    roast_state.malicious_signers.insert(index);
-                    if roast_state.malicious_signers.len() > self.n_signers - self.threshold {
-                        return Err(RoastError::TooFewHonest);
-                    }
+                    malicious_signers,
+                    invalid_shares,
+                    signer_records,
+                )?
+            {
+                return Ok(RoastResponse {
+                    recipients: vec![index],
+                    message: Some(CoordinatorMessage::Banned),
+                });
+            }
 
+            // if we have t-of-n, combine!
+            let message = &message_state.message;
+            let roast_session = message_state
+                .sessions
+                .get_mut(&session_id)
+                .expect("owed session is open");
+            if roast_session.sig_shares.len() >= self.threshold {
+                let session = roast_session.prepared.0.get_or_insert_with(|| {
+                    self.threshold_scheme.start_sign_session(
+                        &self.joint_key,
+                        roast_session.nonces.clone(),
+                        message,
+                    )
+                });
+                let combined_sig = self.threshold_scheme.combine_signature_shares(
+                    &self.joint_key,
+                    session,
+                    roast_session.sig_shares.values().cloned().collect(),
+                );
+                let mut unverified: Vec<_> = roast_session.unverified.keys().copied().collect();
+                unverified.sort_unstable();
+                let valid =
+                    self.threshold_scheme
+                        .verify_signature(&self.joint_key, message, &combined_sig);
+                if valid {
+                    for i in unverified {
+                        let position = roast_session.unverified[&i];
+                        self.accept_share(signer_records, i, session_id, position);
+                    }
+                    self.emit(CoordinatorEvent::SignatureCombined {
+                        session_id,
+                        signature: combined_sig.clone(),
+                    });
+                    // The remaining sign sessions and nonces are no longer needed
+                    message_state.sessions.clear();
+                    message_state.latest_nonces.clear();
+                    message_state.responsive_signers.clear();
+                    message_state.signature = Some(combined_sig.clone());
+                    // return combined signature
+                    return Ok(RoastResponse {
+                        recipients: (0..self.n_signers).collect(),
+                        message: Some(CoordinatorMessage::Completed {
+                            signature: combined_sig,
+                        }),
+                    });
+                }
+                self.emit(CoordinatorEvent::SignatureRejected { session_id });
+                // Only when the combined signature is invalid are the shares checked to find the
+                // culprits
+                for i in unverified {
+                    self.check_share(
+                        message_state,
+                        session_id,
+                        i,
+                        malicious_signers,
+                        invalid_shares,
+                        signer_records,
+                    )?;
+                }
+                // The combined signature was invalid, so the session cannot complete
+                if malicious_signers.contains(&index) {
                     return Ok(RoastResponse {
                         recipients: vec![index],
-                        combined_signature: None,
-                        nonce_set: None,
+                        message: Some(CoordinatorMessage::Banned),
                     });
                 }
-
-                // Reopen session within the roast state for writting
-                let mut roast_session = roast_state
+                let roast_session = message_state
                     .sessions
-                    .get(&session_id)
-                    .unwrap()
-                    .lock()
-                    .expect("got lock");
-
-                // Store valid signature
-                roast_session
-                    .sig_shares
-                    .push(signature_share.expect("party provided None signature share"));
-                println!("New signature from party {}", index);
-
-                // if we have t-of-n, combine!
+                    .get_mut(&session_id)
+                    .expect("owed session is open");
                 if roast_session.sig_shares.len() >= self.threshold {
-                    println!("We have the threshold number of signatures, combining!");
-                    dbg!(&roast_session.sig_shares);
-                    let combined_sig = self.threshold_scheme.combine_signature_shares(
-                        self.joint_key.clone(),
-                        nonces.clone(),
-                        roast_session.sig_shares.clone(),
-                        roast_state.message,
-                    );
-                    // return combined signature
-                    return Ok(RoastResponse {
-                        recipients: (0..self.n_signers).collect(),
-                        combined_signature: Some(combined_sig),
-                        nonce_set: None,
+                    // No share is to blame, so never broadcast the signature and fail the session.
+                    // The failed session is kept with its shares, so that retransmissions and late
+                    // duplicates of them are still recognised, but it never takes another share.
+                    roast_session.failed = true;
+                    let mut signature_shares: Vec<_> = roast_session
+                        .sig_shares
+                        .iter()
+                        .map(|(i, share)| (*i, share.clone()))
+                        .collect();
+                    signature_shares.sort_unstable_by_key(|(i, _)| *i);
+                    failure = Some(RoastError::InvalidSignature {
+                        session_id,
+                        nonce_set: roast_session.nonces.clone(),
+                        signature_shares,
                     });
                 }
             }
-            None => {}
         }
 
         // Store the recieved presignature shares
-        roast_state.latest_nonces.insert(index, new_nonce);
+        message_state.latest_nonces.insert(index, new_nonce);
 
         // Mark S_i as responsive
-        println!("Marked {} as responsive", index.clone());
-        roast_state.responsive_signers.insert(index);
-
-        // if we now have t responsive signers:
-        if roast_state.responsive_signers.len() >= self.threshold {
-            println!("We now have threshold number of responsive signers!");
-            dbg!(&roast_state.responsive_signers);
-            roast_state.session_counter += 1;
-
-            // Look up the nonces
-            let r_signers = roast_state.responsive_signers.clone();
-            // we're not actually aggregating any nonces within the coordinator
-            // This is a change that would belong in the schnorr_fun frost code.
-            let nonces: Vec<_> = r_signers
-                .iter()
-                .cloned()
-                .map(|i| {
-                    (
-                        i,
-                        *roast_state
-                            .latest_nonces
-                            .get(&i)
-                            .expect("has submitted nonce"),
-                    )
-                })
-                .collect();
-
-            let sid = roast_state.session_counter.clone();
-            // Clear responsive signers for following rounds
-            roast_state.responsive_signers = HashSet::new();
-            roast_state.sessions.insert(
-                sid,
-                Arc::new(Mutex::new(RoastSignSession {
-                    signers: r_signers.clone(),
-                    nonces: nonces.clone(),
-                    sig_shares: vec![],
-                })),
-            );
-
-            // Remember the session for signers S_i
-            for i in &r_signers {
-                roast_state.signer_session_map.insert(*i, sid);
-            }
+        message_state.responsive_signers.insert(index);
+        self.emit(CoordinatorEvent::SignerResponsive { index });
+        signer_records.entry(index).or_default().times_responsive += 1;
+        if let Some(error) = failure {
+            return Err(error);
+        }
 
-            // Send nonces to each signer S_i
-            return Ok(RoastResponse {
-                recipients: r_signers.into_iter().collect(),
-                combined_signature: None,
-                nonce_set: Some(nonces),
-            });
+        // if we now have enough responsive signers:
+        let honest_signers = self.n_signers - malicious_signers.len();
+        let pool_size = self
+            .selection
+            .lock()
+            .expect("got lock")
+            .pool_size(self.threshold)
+            .min(honest_signers);
+        if message_state.responsive_signers.len() >= pool_size.max(self.threshold) {
+            self.verify_pending_shares(
+                message_state,
+                malicious_signers,
+                invalid_shares,
+                signer_records,
+            )?;
+            if malicious_signers.contains(&index) {
+                return Ok(RoastResponse {
+                    recipients: vec![index],
+                    message: Some(CoordinatorMessage::Banned),
+                });
+            }
+            if message_state.responsive_signers.len() >= self.threshold {
+                return Ok(self.open_sign_session(message_state, signer_records));
+            }
         }
 
-        // (None, Some(roast_state.latest_nonces))
-        return Ok(RoastResponse {
+        Ok(RoastResponse {
             recipients: vec![index],
-            combined_signature: None,
-            nonce_set: None,
+            message: None,
+        })
+    }
+
+    /// Check the unchecked signature shares of the responsive signers, banning those sending an
+    /// invalid share
+    ///
+    /// With optimistic verification a signer is marked responsive before its share is checked, so
+    /// its share is checked before it can be asked to sign again. A signer sending invalid shares
+    /// can then join no more sign sessions than it could with every share checked.
+    fn verify_pending_shares(
+        &self,
+        message_state: &mut SchemeMessageState<S, K>,
+        malicious_signers: &mut HashSet<usize>,
+        invalid_shares: &mut HashMap<usize, SchemeInvalidShare<S, K>>,
+        signer_records: &mut HashMap<usize, SignerRecord>,
+    ) -> Result<(), SchemeError<S, K>> {
+        let mut responsive: Vec<_> = message_state.responsive_signers.iter().copied().collect();
+        responsive.sort_unstable();
+        for index in responsive {
+            let session_id = match message_state.signer_session_map.get(&index) {
+                Some(session_id) => *session_id,
+                None => continue,
+            };
+            self.check_share(
+                message_state,
+                session_id,
+                index,
+                malicious_signers,
+                invalid_shares,
+                signer_records,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Check a signer's unchecked signature share in a sign session
+    ///
+    /// A valid share is counted towards the signer's record. An invalid one is removed, and its
+    /// signer is no longer responsive and is banned, keeping the share as evidence.
+    ///
+    /// # Returns
+    ///
+    /// Returns whether the share is valid, which it is taken to be if there is no unchecked share.
+    fn check_share(
+        &self,
+        message_state: &mut SchemeMessageState<S, K>,
+        session_id: usize,
+        index: usize,
+        malicious_signers: &mut HashSet<usize>,
+        invalid_shares: &mut HashMap<usize, SchemeInvalidShare<S, K>>,
+        signer_records: &mut HashMap<usize, SignerRecord>,
+    ) -> Result<bool, SchemeError<S, K>> {
+        let message = &message_state.message;
+        let roast_session = match message_state.sessions.get_mut(&session_id) {
+            Some(roast_session) => roast_session,
+            None => return Ok(true),
+        };
+        let position = match roast_session.unverified.remove(&index) {
+            Some(position) => position,
+            None => return Ok(true),
+        };
+        let session = roast_session.prepared.0.get_or_insert_with(|| {
+            self.threshold_scheme.start_sign_session(
+                &self.joint_key,
+                roast_session.nonces.clone(),
+                message,
+            )
         });
+        let share = roast_session.sig_shares[&index].clone();
+        if self.threshold_scheme.verify_signature_share(
+            &self.joint_key,
+            session,
+            index,
+            share.clone(),
+        ) {
+            self.accept_share(signer_records, index, session_id, position);
+            return Ok(true);
+        }
+        roast_session.sig_shares.remove(&index);
+        message_state.responsive_signers.remove(&index);
+        self.ban_invalid_share(
+            malicious_signers,
+            invalid_shares,
+            index,
+            InvalidShare {
+                message: message.clone(),
+                session_id,
+                nonce_set: roast_session.nonces.clone(),
+                signature_share: share,
+            },
+        )?;
+        Ok(false)
+    }
+
+    /// Count a valid signature share towards its signer's record
+    fn accept_share(
+        &self,
+        signer_records: &mut HashMap<usize, SignerRecord>,
+        index: usize,
+        session_id: usize,
+        position: usize,
+    ) {
+        let record = signer_records.entry(index).or_default();
+        record.shares_accepted += 1;
+        record.share_positions += position;
+        self.emit(CoordinatorEvent::ShareAccepted { index, session_id });
+    }
+
+    /// Mark a signer malicious for an invalid signature share, keeping the evidence
+    fn ban_invalid_share(
+        &self,
+        malicious_signers: &mut HashSet<usize>,
+        invalid_shares: &mut HashMap<usize, SchemeInvalidShare<S, K>>,
+        index: usize,
+        invalid_share: SchemeInvalidShare<S, K>,
+    ) -> Result<(), SchemeError<S, K>> {
+        self.emit(CoordinatorEvent::ShareRejected {
+            index,
+            session_id: invalid_share.session_id,
+        });
+        invalid_shares.insert(index, invalid_share);
+        self.ban(malicious_signers, index, BanReason::InvalidSignatureShare)
+    }
+
+    /// Mark a signer malicious
+    ///
+    /// # Returns
+    ///
+    /// Returns [`RoastError::TooFewHonest`] if too many signers are now malicious to sign.
+    fn ban(
+        &self,
+        malicious_signers: &mut HashSet<usize>,
+        index: usize,
+        reason: BanReason,
+    ) -> Result<(), SchemeError<S, K>> {
+        malicious_signers.insert(index);
+        self.emit(CoordinatorEvent::SignerBanned { index, reason });
+        if malicious_signers.len() > self.n_signers - self.threshold {
+            return Err(RoastError::TooFewHonest);
+        }
+        Ok(())
+    }
+
+    /// Open a sign session with `threshold` of the responsive signers, chosen by the
+    /// [`SignerSelection`]
+    fn open_sign_session(
+        &self,
+        message_state: &mut SchemeMessageState<S, K>,
+        signer_records: &mut HashMap<usize, SignerRecord>,
+    ) -> SchemeResponse<S, K, T> {
+        let mut candidates: Vec<usize> = message_state.responsive_signers.iter().copied().collect();
+        candidates.sort_unstable();
+        let selected = self.selection.lock().expect("got lock").select(
+            &candidates,
+            self.threshold,
+            signer_records,
+        );
+        // Keep only distinct candidates, topping up in index order if too few were chosen
+        let mut r_signers = HashSet::new();
+        for i in selected.into_iter().chain(candidates.iter().copied()) {
+            if r_signers.len() == self.threshold {
+                break;
+            }
+            if message_state.responsive_signers.contains(&i) {
+                r_signers.insert(i);
+            }
+        }
+
+        message_state.session_counter += 1;
+
+        // Look up the nonces
+        // we're not actually aggregating any nonces within the coordinator
+        // This is a change that would belong in the schnorr_fun frost code.
+        let nonces: Vec<_> = r_signers
+            .iter()
+            .cloned()
+            .map(|i| {
+                (
+                    i,
+                    message_state
+                        .latest_nonces
+                        .get(&i)
+                        .expect("has submitted nonce")
+                        .clone(),
+                )
+            })
+            .collect();
+
+        let sid = message_state.session_counter;
+        // Remove the selected signers from the responsive pool for following rounds
+        message_state
+            .responsive_signers
+            .retain(|i| !r_signers.contains(i));
+        message_state.sessions.insert(
+            sid,
+            RoastSignSession {
+                signers: r_signers.clone(),
+                nonces: nonces.clone(),
+                sig_shares: HashMap::new(),
+                failed: false,
+                unverified: HashMap::new(),
+                prepared: Prepared::default(),
+            },
+        );
+
+        // Remember the session for signers S_i
+        for i in &r_signers {
+            message_state.signer_session_map.insert(*i, sid);
+            signer_records.entry(*i).or_default().sessions_joined += 1;
+        }
+        self.emit(CoordinatorEvent::SignSessionOpened {
+            session_id: sid,
+            signers: r_signers.iter().copied().collect(),
+        });
+
+        // Send nonces to each signer S_i
+        RoastResponse {
+            recipients: r_signers.into_iter().collect(),
+            message: Some(self.sign_request(&message_state.message, sid, nonces)),
+        }
+    }
+
+    /// The request to sign in a sign session, announced if we have an announcement key
+    fn sign_request(
+        &self,
+        message: &S::Message,
+        session_id: usize,
+        nonce_set: Vec<(usize, S::PublicNonce)>,
+    ) -> SchemeCoordinatorMessage<S, K, T> {
+        let announcement_signature = self.announcement_key.as_ref().map(|announcement_key| {
+            SessionAnnouncement::sign(
+                &self.announcement_scheme,
+                announcement_key,
+                message.clone(),
+                session_id,
+                nonce_set.clone(),
+            )
+            .signature
+        });
+        CoordinatorMessage::SignRequest {
+            message_id: message_id(message),
+            session_id,
+            nonce_set,
+            announcement_signature,
+        }
     }
 }
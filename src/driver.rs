@@ -0,0 +1,223 @@
+//! ROAST Async Driver
+//!
+//! Runs a [`Coordinator`] and any number of [`RoastSigner`]s over tokio `mpsc` channels, delivering
+//! every [`RoastResponse`] to its recipients until the message is signed.
+//!
+//! Enabled by the `async` feature.
+//!
+//! [`RoastResponse`]: crate::coordinator::RoastResponse
+use std::{collections::HashMap, fmt, time::Duration};
+
+use rand::{rngs::StdRng, RngCore, SeedableRng};
+use schnorr_fun::musig::Nonce;
+use secp256kfun::{
+    marker::{Public, Zero},
+    Scalar,
+};
+use tokio::{
+    runtime::{Handle, RuntimeFlavor},
+    sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender},
+};
+
+use crate::{
+    announcement::AnnouncementScheme,
+    coordinator::{Coordinator, RoastError},
+    protocol::{CoordinatorMessage, SchemeCoordinatorMessage, SchemeSignerMessage},
+    signer::RoastSigner,
+    threshold_scheme::ThresholdScheme,
+};
+
+/// An error of a driven run, with the [`RoastError`] type parameters of its threshold scheme
+#[derive(Debug, Clone, PartialEq)]
+pub enum DriverError<N = Nonce, Z = Scalar<Public, Zero>> {
+    /// The coordinator can no longer produce a signature
+    Coordinator(RoastError<N, Z>),
+    /// Every signer stopped before the message was signed
+    Disconnected,
+}
+
+/// The [`DriverError`] of a run using the threshold scheme `S`
+pub type SchemeDriverError<S, K> =
+    DriverError<<S as ThresholdScheme<K>>::PublicNonce, <S as ThresholdScheme<K>>::SignatureShare>;
+
+impl<N, Z> fmt::Display for DriverError<N, Z> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Coordinator(error) => write!(f, "Coordinator failed: {}", error),
+            Self::Disconnected => write!(f, "All signers disconnected before signing"),
+        }
+    }
+}
+
+impl<N: fmt::Debug, Z: fmt::Debug> std::error::Error for DriverError<N, Z> {}
+
+type SignerWithMessage<S, K, T> = (RoastSigner<S, K, T>, SchemeSignerMessage<S, K>);
+
+/// How long [`sign`] waits for a message before opening a session with the responsive signers
+pub const DEFAULT_IDLE_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Run a coordinator until the message is signed
+///
+/// Messages are read from `inbox` along with the index of the signer who sent them, and each
+/// response is sent to the `outboxes` of its recipients. Malformed messages are dropped, as they
+/// leave the coordinator state untouched. After a sign session fails to produce a valid signature,
+/// a new session is opened with the responsive signers.
+///
+/// Whenever no message arrives for `idle_timeout`, [`Coordinator::open_session`] is called, so that
+/// absent signers cannot stall a [`SignerSelection`] pool larger than the threshold.
+///
+/// [`Coordinator::start_session`] must have been called before running, and the runtime must have
+/// its time driver enabled.
+///
+/// The coordinator saves its state to its [`StateStore`] while handling each message, which may
+/// block. On a multi-threaded runtime the coordinator is called through
+/// [`tokio::task::block_in_place`], so other tasks keep running. On a current-thread runtime it is
+/// called directly, so only use a store which does not block, such as a [`MemoryStore`].
+///
+/// [`StateStore`]: crate::storage::StateStore
+/// [`MemoryStore`]: crate::storage::MemoryStore
+/// [`SignerSelection`]: crate::selection::SignerSelection
+///
+/// # Returns
+///
+/// Returns the combined signature, or a [`DriverError`] if the coordinator fails or every signer
+/// hangs up first.
+pub async fn run_coordinator<S: ThresholdScheme<K>, K: Clone, T: AnnouncementScheme>(
+    coordinator: &Coordinator<S, K, T>,
+    mut inbox: UnboundedReceiver<(usize, SchemeSignerMessage<S, K>)>,
+    outboxes: &HashMap<usize, UnboundedSender<SchemeCoordinatorMessage<S, K, T>>>,
+    idle_timeout: Duration,
+) -> Result<S::Signature, SchemeDriverError<S, K>> {
+    loop {
+        let received = match tokio::time::timeout(idle_timeout, inbox.recv()).await {
+            Ok(Some((index, message))) => Some(blocking(|| coordinator.receive(index, message))),
+            Ok(None) => return Err(DriverError::Disconnected),
+            // The pool may be waiting on absent signers
+            Err(_) => None,
+        };
+        let response = match received {
+            Some(Ok(response)) => response,
+            Some(Err(
+                error @ (RoastError::TooFewHonest
+                | RoastError::NoActiveSession
+                | RoastError::Storage(_)),
+            )) => return Err(DriverError::Coordinator(error)),
+            Some(Err(RoastError::InvalidSignature { .. })) | None => {
+                match blocking(|| coordinator.open_session()) {
+                    Ok(Some(response)) => response,
+                    Ok(None) => continue,
+                    Err(error) => return Err(DriverError::Coordinator(error)),
+                }
+            }
+            Some(Err(_)) => continue,
+        };
+        let message = match response.message {
+            Some(message) => message,
+            None => continue,
+        };
+        for recipient in &response.recipients {
+            if let Some(outbox) = outboxes.get(recipient) {
+                // A signer which has stopped has no need for the message
+                let _ = outbox.send(message.clone());
+            }
+        }
+        if let CoordinatorMessage::Completed { signature } = message {
+            return Ok(signature);
+        }
+    }
+}
+
+/// Make a call which may block, moving the other tasks off this worker thread if the runtime has any
+/// others
+fn blocking<R>(call: impl FnOnce() -> R) -> R {
+    match Handle::current().runtime_flavor() {
+        RuntimeFlavor::MultiThread => tokio::task::block_in_place(call),
+        _ => call(),
+    }
+}
+
+/// Run a signer until the coordinator announces a signature or bans it
+///
+/// The signer's initial message is sent first, then every [`CoordinatorMessage`] read from `inbox`
+/// is answered on `outbox`. A sign request the signer refuses, such as one with an unsafe nonce
+/// set, is left unanswered, as in the [`simulator`].
+///
+/// # Returns
+///
+/// Returns the combined signature, or `None` if the signer was banned or the coordinator hung up.
+///
+/// [`simulator`]: crate::simulator
+pub async fn run_signer<S: ThresholdScheme<K> + Clone, K: Clone, T: AnnouncementScheme>(
+    mut signer: RoastSigner<S, K, T>,
+    initial_message: SchemeSignerMessage<S, K>,
+    mut nonce_rng: impl RngCore,
+    mut inbox: UnboundedReceiver<SchemeCoordinatorMessage<S, K, T>>,
+    outbox: UnboundedSender<(usize, SchemeSignerMessage<S, K>)>,
+) -> Option<S::Signature> {
+    let index = signer.index();
+    if outbox.send((index, initial_message)).is_err() {
+        return None;
+    }
+    while let Some(message) = inbox.recv().await {
+        let banned = message == CoordinatorMessage::Banned;
+        // A signer refusing an unsafe nonce set simply does not reply
+        if let Ok(Some(reply)) = signer.receive(&mut nonce_rng, message) {
+            if outbox.send((index, reply)).is_err() {
+                return None;
+            }
+        }
+        if banned || signer.signature().is_some() {
+            break;
+        }
+    }
+    signer.signature()
+}
+
+/// Sign with a coordinator and a set of signers, each signer running as its own tokio task
+///
+/// Each signer is given along with its initial message, as returned by [`RoastSigner::new`]. Any
+/// signers of the group which are not given are treated as absent. The coordinator is run with
+/// [`DEFAULT_IDLE_TIMEOUT`].
+///
+/// Must be called from within a tokio runtime with its time driver enabled, see [`run_coordinator`]
+/// for which state stores suit which runtimes.
+pub async fn sign<S, K, T>(
+    coordinator: &Coordinator<S, K, T>,
+    signers: Vec<SignerWithMessage<S, K, T>>,
+) -> Result<S::Signature, SchemeDriverError<S, K>>
+where
+    S: ThresholdScheme<K> + Clone + Send + 'static,
+    K: Clone + Send + 'static,
+    S::PublicNonce: Send,
+    S::SecretNonce: Send,
+    S::SecretShare: Send,
+    S::SignatureShare: Send,
+    S::Signature: Send,
+    S::Message: Send,
+    T: AnnouncementScheme + Send + 'static,
+    T::CoordinatorKey: Send,
+    T::AnnouncementSignature: Send,
+{
+    let (coordinator_outbox, coordinator_inbox) = unbounded_channel();
+    let mut outboxes = HashMap::new();
+    for (signer, initial_message) in signers {
+        let (outbox, inbox) = unbounded_channel();
+        outboxes.insert(signer.index(), outbox);
+        tokio::spawn(run_signer(
+            signer,
+            initial_message,
+            StdRng::from_entropy(),
+            inbox,
+            coordinator_outbox.clone(),
+        ));
+    }
+    // Only the signers hold senders, so the inbox closes once they have all stopped
+    drop(coordinator_outbox);
+    run_coordinator(
+        coordinator,
+        coordinator_inbox,
+        &outboxes,
+        DEFAULT_IDLE_TIMEOUT,
+    )
+    .await
+}
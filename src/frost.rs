@@ -1,7 +1,7 @@
 use schnorr_fun::{
-    frost::{Frost, FrostKey},
+    frost::{Frost, FrostKey, SignSession},
     musig::{Nonce, NonceKeyPair},
-    Message, Signature,
+    Signature,
 };
 use secp256kfun::{
     digest::typenum::U32,
@@ -10,48 +10,81 @@ use secp256kfun::{
 };
 use sha2::Digest;
 
-use crate::threshold_scheme::ThresholdScheme;
+use crate::{coordinator::StoredMessage, threshold_scheme::ThresholdScheme};
 
 impl<H: Digest + Clone + Digest<OutputSize = U32>, NG> ThresholdScheme<FrostKey<EvenY>>
     for Frost<H, NG>
 {
-    fn gen_nonce<R: rand::RngCore>(&self, nonce_rng: &mut R) -> schnorr_fun::musig::NonceKeyPair {
+    type PublicNonce = Nonce;
+    type SecretNonce = NonceKeyPair;
+    type SecretShare = Scalar;
+    type SignatureShare = Scalar<Public, Zero>;
+    type Signature = Signature;
+    type Message = StoredMessage;
+    type SignSession = SignSession;
+
+    fn threshold(&self, joint_key: &FrostKey<EvenY>) -> usize {
+        joint_key.threshold()
+    }
+
+    fn n_signers(&self, joint_key: &FrostKey<EvenY>) -> usize {
+        joint_key.n_signers()
+    }
+
+    fn gen_nonce<R: rand::RngCore>(&self, nonce_rng: &mut R) -> NonceKeyPair {
         NonceKeyPair::random(nonce_rng)
     }
 
-    fn sign(
+    fn public_nonce(&self, secret_nonce: &NonceKeyPair) -> Nonce {
+        secret_nonce.public()
+    }
+
+    fn start_sign_session(
         &self,
-        joint_key: FrostKey<EvenY>,
+        joint_key: &FrostKey<EvenY>,
         nonces: Vec<(usize, Nonce)>,
+        message: &StoredMessage,
+    ) -> SignSession {
+        self.start_sign_session(joint_key, nonces, message.as_message())
+    }
+
+    fn sign(
+        &self,
+        joint_key: &FrostKey<EvenY>,
+        session: &SignSession,
         my_index: usize,
         secret_share: &Scalar,
-        secret_nonce: schnorr_fun::musig::NonceKeyPair,
-        message: Message,
+        secret_nonce: NonceKeyPair,
     ) -> Scalar<Public, Zero> {
-        let session = self.start_sign_session(&joint_key, nonces, message);
-        self.sign(&joint_key, &session, my_index, &secret_share, secret_nonce)
+        self.sign(joint_key, session, my_index, secret_share, secret_nonce)
     }
 
     fn verify_signature_share(
         &self,
-        joint_key: FrostKey<EvenY>,
-        nonces: Vec<(usize, Nonce)>,
+        joint_key: &FrostKey<EvenY>,
+        session: &SignSession,
         index: usize,
         signature_share: Scalar<Public, Zero>,
-        message: Message,
     ) -> bool {
-        let sign_session = self.start_sign_session(&joint_key, nonces, message);
-        self.verify_signature_share(&joint_key, &sign_session, index, signature_share)
+        self.verify_signature_share(joint_key, session, index, signature_share)
     }
 
     fn combine_signature_shares(
         &self,
-        joint_key: FrostKey<EvenY>,
-        nonces: Vec<(usize, Nonce)>,
+        joint_key: &FrostKey<EvenY>,
+        session: &SignSession,
         signature_shares: Vec<Scalar<Public, Zero>>,
-        message: Message,
     ) -> Signature {
-        let sign_session = self.start_sign_session(&joint_key, nonces, message);
-        self.combine_signature_shares(&joint_key, &sign_session, signature_shares)
+        self.combine_signature_shares(joint_key, session, signature_shares)
+    }
+
+    fn verify_signature(
+        &self,
+        joint_key: &FrostKey<EvenY>,
+        message: &StoredMessage,
+        signature: &Signature,
+    ) -> bool {
+        self.schnorr
+            .verify(&joint_key.public_key(), message.as_message(), signature)
     }
 }
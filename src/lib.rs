@@ -7,10 +7,12 @@
 //! valid signature, regardless of the presence of absent or malicious signers.
 //!
 //! > ⚠ At this stage this implementation is for API exploration purposes only. It has not been
-//! reviewed or vetted, and should be considered insecure for practical purposes.
+//! > reviewed or vetted, and should be considered insecure for practical purposes.
 //!
-//! Much of the communication and interaction between the signer and coordinator is missing,
-//! including any asynchronicity (hopefully can be built around the existing core functions).
+//! Much of the communication and interaction between the signer and coordinator is missing. With
+//! the `async` feature, the `driver` module runs a coordinator and its signers as tokio tasks. The
+//! `simulator` module replays runs over a simulated network, and the `transport` module injects
+//! network faults into any other message transport.
 //!
 //! ## ROAST Summary
 //!
@@ -20,21 +22,39 @@
 //! a new nonce that will be used for future signing sessions.
 //!
 //! Special care is taken to track responsive signers and malicious signers, ensuring we will
-//! eventually arrive upon a signature.
+//! eventually arrive upon a signature. Which responsive signers form each session can be changed
+//! with a `selection::SignerSelection` strategy.
 //!
 //! ## Usage Notes
 //!
 //! In theory, each signer can also be a coordinator, allowing for symmetric design accross
 //! participants of the multisignature - though this library has not yet been tested in this way.
 //!
+//! To avoid trusting a single coordinator, a signer can take part in the runs of `n - t + 1`
+//! coordinators at once with a `manager::SignerManager`.
+//!
 //! Currently this ROAST implementation was designed for *[secp256kfun FROST]*, but it is written to
 //! be agnostic as to which threshold signature scheme is used. See `threshold_scheme.rs`.
 //!
 //! [secp256kfun FROST]: <https://docs.rs/schnorr_fun/latest/schnorr_fun/frost/index.html>
 
+pub mod adversary;
+pub mod announcement;
+pub mod blame;
 pub mod coordinator;
+#[cfg(feature = "async")]
+pub mod driver;
+pub mod link;
+pub mod manager;
+pub mod observer;
+pub mod protocol;
+pub mod selection;
 pub mod signer;
+pub mod simulator;
+pub mod storage;
 pub mod threshold_scheme;
+pub mod transport;
+pub mod wire;
 
 #[cfg(feature = "frost")]
 pub mod frost;
@@ -0,0 +1,49 @@
+//! ROAST Network Links
+//!
+//! A [`Link`] describes how messages travel one way between the coordinator and a signer, for the
+//! [`simulator`] and for a [`FaultyTransport`].
+//!
+//! [`simulator`]: crate::simulator
+//! [`FaultyTransport`]: crate::transport::FaultyTransport
+use rand::{rngs::StdRng, Rng};
+
+/// How long a message spends in flight, in ticks of the virtual clock
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Latency {
+    /// Always the same delay
+    Fixed(u64),
+    /// A delay drawn uniformly from `min..=max`
+    Uniform { min: u64, max: u64 },
+    /// An exponentially distributed delay with the given mean, giving a long tail of slow messages
+    Exponential { mean: f64 },
+}
+
+impl Latency {
+    pub(crate) fn sample(&self, rng: &mut StdRng) -> u64 {
+        match *self {
+            Latency::Fixed(delay) => delay,
+            Latency::Uniform { min, max } => rng.gen_range(min..=max.max(min)),
+            Latency::Exponential { mean } => (-mean * (1.0 - rng.gen::<f64>()).ln()).round() as u64,
+        }
+    }
+}
+
+/// A one-way connection between the coordinator and a signer
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Link {
+    pub latency: Latency,
+    /// The probability that a message is lost
+    pub drop_rate: f64,
+    /// Whether a message may overtake one sent before it, otherwise the link is first-in first-out
+    pub reorder: bool,
+}
+
+impl Default for Link {
+    fn default() -> Self {
+        Self {
+            latency: Latency::Fixed(1),
+            drop_rate: 0.0,
+            reorder: false,
+        }
+    }
+}
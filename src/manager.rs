@@ -0,0 +1,186 @@
+//! ROAST Signer Manager
+//!
+//! A single coordinator can stall signing forever by never opening sessions. The ROAST paper
+//! removes this trust by running `n - t + 1` coordinators for the same message, so at least one
+//! of them is honest whenever `t` signers are. A [`SignerManager`] takes part in every one of
+//! these coordinator runs on behalf of one signer.
+//!
+//! Each run has its own [`RoastSigner`], so nonces are never shared between coordinators. Runs
+//! are started one after another with a staggered delay, so that in the common case the first
+//! coordinator finishes before the others are bothered. As soon as any coordinator announces a
+//! valid signature every run is shut down and its nonces erased.
+use std::time::Duration;
+
+use rand::RngCore;
+
+use crate::{
+    protocol::{CoordinatorMessage, SchemeCoordinatorMessage, SchemeSignerMessage},
+    signer::{RoastSigner, SignerError},
+    threshold_scheme::ThresholdScheme,
+};
+
+/// Our participation in a single coordinator's run
+enum Run<S: ThresholdScheme<K>, K: Clone> {
+    /// The run's start delay has not yet passed
+    Pending,
+    Running(Box<RoastSigner<S, K>>),
+    /// The coordinator announced an invalid signature, so we no longer take part
+    Abandoned,
+}
+
+pub struct SignerManager<S: ThresholdScheme<K>, K: Clone> {
+    threshold_scheme: S,
+    joint_key: K,
+    my_index: usize,
+    secret_share: S::SecretShare,
+    message: S::Message,
+    stagger: Duration,
+    runs: Vec<Run<S, K>>,
+    signature: Option<S::Signature>,
+}
+
+impl<S: ThresholdScheme<K> + Clone, K: Clone> SignerManager<S, K> {
+    /// Create a manager taking part in a run with each of `n_coordinators` coordinators
+    ///
+    /// Coordinator `i` is started once `i * stagger` has elapsed, see [`SignerManager::start_due`].
+    pub fn new(
+        threshold_scheme: S,
+        joint_key: K,
+        my_index: usize,
+        secret_share: S::SecretShare,
+        message: impl Into<S::Message>,
+        n_coordinators: usize,
+        stagger: Duration,
+    ) -> Self {
+        Self {
+            threshold_scheme,
+            joint_key,
+            my_index,
+            secret_share,
+            message: message.into(),
+            stagger,
+            runs: (0..n_coordinators).map(|_| Run::Pending).collect(),
+            signature: None,
+        }
+    }
+
+    /// Create a manager for the `n - t + 1` coordinators needed for at least one to be honest
+    pub fn for_group(
+        threshold_scheme: S,
+        joint_key: K,
+        my_index: usize,
+        secret_share: S::SecretShare,
+        message: impl Into<S::Message>,
+        stagger: Duration,
+    ) -> Self {
+        let n_coordinators =
+            threshold_scheme.n_signers(&joint_key) - threshold_scheme.threshold(&joint_key) + 1;
+        Self::new(
+            threshold_scheme,
+            joint_key,
+            my_index,
+            secret_share,
+            message,
+            n_coordinators,
+            stagger,
+        )
+    }
+
+    /// Start every run whose delay has passed
+    ///
+    /// `elapsed` is the time since signing began.
+    ///
+    /// # Returns
+    ///
+    /// Returns the initial [`SignerMessage::NonceOnly`] for each newly started coordinator, along
+    /// with the coordinator's index.
+    ///
+    /// [`SignerMessage::NonceOnly`]: crate::protocol::SignerMessage::NonceOnly
+    pub fn start_due(
+        &mut self,
+        nonce_rng: &mut impl RngCore,
+        elapsed: Duration,
+    ) -> Vec<(usize, SchemeSignerMessage<S, K>)> {
+        let mut started = vec![];
+        for (coordinator, run) in self.runs.iter_mut().enumerate() {
+            if !matches!(run, Run::Pending) || self.stagger * coordinator as u32 > elapsed {
+                continue;
+            }
+            let (signer, nonce) = RoastSigner::new(
+                nonce_rng,
+                self.threshold_scheme.clone(),
+                self.joint_key.clone(),
+                self.my_index,
+                self.secret_share.clone(),
+                self.message.clone(),
+            );
+            *run = Run::Running(Box::new(signer));
+            started.push((coordinator, nonce));
+        }
+        started
+    }
+
+    /// The time until the next run is due to start, if any are still pending
+    pub fn next_start(&self, elapsed: Duration) -> Option<Duration> {
+        self.runs
+            .iter()
+            .position(|run| matches!(run, Run::Pending))
+            .map(|coordinator| {
+                (self.stagger * coordinator as u32)
+                    .checked_sub(elapsed)
+                    .unwrap_or_default()
+            })
+    }
+
+    /// Receive a message from one of the coordinators
+    ///
+    /// Messages from coordinators which have not been started, have been abandoned, or arrive
+    /// after signing has finished are ignored.
+    ///
+    /// # Returns
+    ///
+    /// Returns the [`SignerMessage`] to reply to that coordinator with, if any
+    ///
+    /// [`SignerMessage`]: crate::protocol::SignerMessage
+    pub fn receive(
+        &mut self,
+        nonce_rng: &mut impl RngCore,
+        coordinator: usize,
+        message: SchemeCoordinatorMessage<S, K>,
+    ) -> Result<Option<SchemeSignerMessage<S, K>>, SignerError> {
+        let run = match self.runs.get_mut(coordinator) {
+            Some(run) => run,
+            None => return Ok(None),
+        };
+        let signer = match run {
+            Run::Running(signer) => signer,
+            Run::Pending | Run::Abandoned => return Ok(None),
+        };
+
+        if let CoordinatorMessage::Completed { signature } = &message {
+            if self
+                .threshold_scheme
+                .verify_signature(&self.joint_key, &self.message, signature)
+            {
+                self.signature = Some(signature.clone());
+                // Shut down every run, erasing their nonces
+                self.runs.clear();
+            } else {
+                *run = Run::Abandoned;
+            }
+            return Ok(None);
+        }
+
+        signer.receive(nonce_rng, message)
+    }
+
+    /// Whether the coordinator's run has been started and is still going
+    pub fn is_running(&self, coordinator: usize) -> bool {
+        matches!(self.runs.get(coordinator), Some(Run::Running(_)))
+    }
+
+    /// The first valid signature announced by any coordinator
+    pub fn signature(&self) -> Option<S::Signature> {
+        self.signature.clone()
+    }
+}
@@ -0,0 +1,67 @@
+//! ROAST Coordinator Observer
+//!
+//! Every state transition of a [`Coordinator`] is described by a [`CoordinatorEvent`] and handed
+//! to its [`CoordinatorObserver`]s, so they can be fed into logging, alerting or audit pipelines.
+//!
+//! [`Coordinator`]: crate::coordinator::Coordinator
+use schnorr_fun::Signature;
+
+/// Why a signer was marked malicious
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BanReason {
+    /// The signer sent a message while it was not expected to
+    UnsolicitedMessage,
+    /// The signer sent a signature share that failed verification, see
+    /// [`Coordinator::blame_proof`](crate::coordinator::Coordinator::blame_proof)
+    InvalidSignatureShare,
+    /// The signer shared a nonce which was already shared for the message being signed
+    ReusedNonce,
+}
+
+/// A state transition of a [`Coordinator`], whose scheme combines signatures of type `Sig`
+///
+/// [`Coordinator`]: crate::coordinator::Coordinator
+#[derive(Debug, Clone, PartialEq)]
+pub enum CoordinatorEvent<Sig = Signature> {
+    /// A signing session was started for a new message
+    SessionStarted,
+    /// A signer provided a nonce and was marked responsive
+    SignerResponsive { index: usize },
+    /// A sign session was opened with the nonces of these signers
+    SignSessionOpened {
+        session_id: usize,
+        signers: Vec<usize>,
+    },
+    /// A valid signature share was received
+    ShareAccepted { index: usize, session_id: usize },
+    /// A signature share failed verification
+    ShareRejected { index: usize, session_id: usize },
+    /// A signer was marked malicious
+    SignerBanned { index: usize, reason: BanReason },
+    /// A message from a signer already marked malicious was ignored
+    BannedSignerIgnored { index: usize },
+    /// A retransmitted message was answered with the previous response
+    RetransmissionReplayed { index: usize },
+    /// A duplicate of a message older than the signer's last was ignored
+    DuplicateIgnored { index: usize },
+    /// The signature shares of a sign session were combined into a signature
+    SignatureCombined { session_id: usize, signature: Sig },
+    /// The signature shares of a sign session combined into a signature which failed verification
+    SignatureRejected { session_id: usize },
+}
+
+/// Receives every [`CoordinatorEvent`] of a [`Coordinator`]
+///
+/// Events are delivered in order while the coordinator holds its state lock, so observers should
+/// return quickly.
+///
+/// [`Coordinator`]: crate::coordinator::Coordinator
+pub trait CoordinatorObserver<Sig = Signature> {
+    fn on_event(&self, event: &CoordinatorEvent<Sig>);
+}
+
+impl<Sig, F: Fn(&CoordinatorEvent<Sig>)> CoordinatorObserver<Sig> for F {
+    fn on_event(&self, event: &CoordinatorEvent<Sig>) {
+        self(event)
+    }
+}
@@ -0,0 +1,113 @@
+//! ROAST Protocol Messages
+//!
+//! The messages exchanged between a [`Coordinator`] and its [`RoastSigner`]s.
+//!
+//! Both sides are sans-IO: they consume and produce these messages without caring how they are
+//! delivered, so any transport can be placed underneath. Messages are serde serializable.
+//!
+//! Every [`SignerMessage`] and sign request carries the [`message_id`] of the message being signed,
+//! so one arriving after its sender or recipient has moved on to another message is rejected
+//! rather than taken for part of the new one.
+//!
+//! [`Coordinator`]: crate::coordinator::Coordinator
+//! [`RoastSigner`]: crate::signer::RoastSigner
+use schnorr_fun::{musig::Nonce, Signature};
+use secp256kfun::{
+    marker::{Public, Zero},
+    Scalar,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    announcement::{AnnouncementScheme, SchnorrAnnouncements},
+    threshold_scheme::ThresholdScheme,
+};
+
+/// A message sent from a signer to the coordinator
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SignerMessage<N = Nonce, Z = Scalar<Public, Zero>> {
+    /// A nonce to be used in a future sign session, sent before being asked to sign
+    NonceOnly { message_id: MessageId, nonce: N },
+    /// A signature share for the sign session the signer was asked to sign in, along with a
+    /// fresh nonce for the next sign session
+    ShareAndNonce {
+        message_id: MessageId,
+        session_id: usize,
+        signature_share: Z,
+        nonce: N,
+    },
+}
+
+/// A message sent from the coordinator to one or more signers
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[allow(clippy::large_enum_variant)] // the scheme's signature size is unknown
+pub enum CoordinatorMessage<N = Nonce, Sig = Signature, A = Signature> {
+    /// Sign the message under this set of nonces, replying with the same `session_id`
+    SignRequest {
+        message_id: MessageId,
+        session_id: usize,
+        nonce_set: Vec<(usize, N)>,
+        /// The coordinator's signature over the [`SessionAnnouncement`], if it has an
+        /// announcement key
+        ///
+        /// [`SessionAnnouncement`]: crate::announcement::SessionAnnouncement
+        #[serde(default = "Option::default")]
+        announcement_signature: Option<A>,
+    },
+    /// The message has been signed
+    Completed { signature: Sig },
+    /// The recipient has been marked malicious and will be ignored
+    Banned,
+}
+
+/// Identifies a message being signed, see [`message_id`]
+pub type MessageId = [u8; 32];
+
+/// The SHA-256 of the JSON encoding of a message being signed
+pub fn message_id<M: Serialize>(message: &M) -> MessageId {
+    let json = serde_json::to_vec(message).expect("scheme types serialize to JSON");
+    Sha256::digest(json).into()
+}
+
+/// The [`SignerMessage`] of the threshold scheme `S`
+pub type SchemeSignerMessage<S, K> = SignerMessage<
+    <S as ThresholdScheme<K>>::PublicNonce,
+    <S as ThresholdScheme<K>>::SignatureShare,
+>;
+
+/// The [`CoordinatorMessage`] of the threshold scheme `S`, announcing sessions with the
+/// announcement scheme `T`
+pub type SchemeCoordinatorMessage<S, K, T = SchnorrAnnouncements> = CoordinatorMessage<
+    <S as ThresholdScheme<K>>::PublicNonce,
+    <S as ThresholdScheme<K>>::Signature,
+    <T as AnnouncementScheme>::AnnouncementSignature,
+>;
+
+impl<N: Clone, Z: Clone> SignerMessage<N, Z> {
+    /// The [`message_id`] of the message being signed
+    pub fn message_id(&self) -> MessageId {
+        match self {
+            Self::NonceOnly { message_id, .. } | Self::ShareAndNonce { message_id, .. } => {
+                *message_id
+            }
+        }
+    }
+
+    /// The fresh nonce carried by every signer message
+    pub fn nonce(&self) -> N {
+        match self {
+            Self::NonceOnly { nonce, .. } | Self::ShareAndNonce { nonce, .. } => nonce.clone(),
+        }
+    }
+
+    /// The signature share, if this message carries one
+    pub fn signature_share(&self) -> Option<Z> {
+        match self {
+            Self::NonceOnly { .. } => None,
+            Self::ShareAndNonce {
+                signature_share, ..
+            } => Some(signature_share.clone()),
+        }
+    }
+}
@@ -0,0 +1,165 @@
+//! ROAST Signer Selection
+//!
+//! A [`SignerSelection`] is consulted whenever a [`Coordinator`] forms a sign session. It decides
+//! how many responsive signers to wait for, and which `threshold` of them to ask to sign.
+//!
+//! Waiting for a pool larger than the threshold gives a strategy a choice of signers, but a
+//! signer that never responds would stall the pool. Callers using such a strategy should call
+//! [`Coordinator::open_session`] on a timer, which opens a session as soon as `threshold` signers
+//! are responsive.
+//!
+//! [`Coordinator`]: crate::coordinator::Coordinator
+//! [`Coordinator::open_session`]: crate::coordinator::Coordinator::open_session
+use std::{cmp::Ordering, collections::HashMap};
+
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+use crate::coordinator::SignerRecord;
+
+/// Chooses the signers of each sign session
+pub trait SignerSelection {
+    /// The number of responsive signers to wait for before opening a session
+    fn pool_size(&self, threshold: usize) -> usize {
+        threshold
+    }
+
+    /// Choose `threshold` signers from the responsive `candidates`, given in index order
+    ///
+    /// Any choice which is not `threshold` distinct candidates is completed by the coordinator
+    /// with the remaining candidates in index order.
+    fn select(
+        &mut self,
+        candidates: &[usize],
+        threshold: usize,
+        records: &HashMap<usize, SignerRecord>,
+    ) -> Vec<usize>;
+}
+
+/// Open a session as soon as `threshold` signers are responsive, as in the ROAST paper
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FirstResponsive;
+
+impl SignerSelection for FirstResponsive {
+    fn select(
+        &mut self,
+        candidates: &[usize],
+        threshold: usize,
+        _records: &HashMap<usize, SignerRecord>,
+    ) -> Vec<usize> {
+        candidates.iter().copied().take(threshold).collect()
+    }
+}
+
+/// Prefer signers whose signature shares have historically arrived earliest in their sessions
+///
+/// Signers without any accepted shares are chosen last.
+#[derive(Debug, Clone, Copy)]
+pub struct FastestSigners {
+    pub pool_size: usize,
+}
+
+impl SignerSelection for FastestSigners {
+    fn pool_size(&self, threshold: usize) -> usize {
+        self.pool_size.max(threshold)
+    }
+
+    fn select(
+        &mut self,
+        candidates: &[usize],
+        threshold: usize,
+        records: &HashMap<usize, SignerRecord>,
+    ) -> Vec<usize> {
+        let position = |index: &usize| {
+            records
+                .get(index)
+                .and_then(SignerRecord::average_share_position)
+        };
+        rank_by(candidates, threshold, |a, b| {
+            match (position(a), position(b)) {
+                (Some(a), Some(b)) => a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            }
+        })
+    }
+}
+
+/// Prefer signers which have provided a share in the largest fraction of the sessions they joined
+///
+/// Signers which have never joined a session are treated as fully reliable.
+#[derive(Debug, Clone, Copy)]
+pub struct MostReliable {
+    pub pool_size: usize,
+}
+
+impl SignerSelection for MostReliable {
+    fn pool_size(&self, threshold: usize) -> usize {
+        self.pool_size.max(threshold)
+    }
+
+    fn select(
+        &mut self,
+        candidates: &[usize],
+        threshold: usize,
+        records: &HashMap<usize, SignerRecord>,
+    ) -> Vec<usize> {
+        let reliability = |index: &usize| {
+            records
+                .get(index)
+                .and_then(SignerRecord::completion_rate)
+                .unwrap_or(1.0)
+        };
+        rank_by(candidates, threshold, |a, b| {
+            reliability(b)
+                .partial_cmp(&reliability(a))
+                .unwrap_or(Ordering::Equal)
+        })
+    }
+}
+
+/// Choose signers uniformly at random from the pool
+#[derive(Debug, Clone)]
+pub struct Randomised {
+    pub pool_size: usize,
+    rng: StdRng,
+}
+
+impl Randomised {
+    pub fn new(pool_size: usize, seed: u64) -> Self {
+        Self {
+            pool_size,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl SignerSelection for Randomised {
+    fn pool_size(&self, threshold: usize) -> usize {
+        self.pool_size.max(threshold)
+    }
+
+    fn select(
+        &mut self,
+        candidates: &[usize],
+        threshold: usize,
+        _records: &HashMap<usize, SignerRecord>,
+    ) -> Vec<usize> {
+        candidates
+            .choose_multiple(&mut self.rng, threshold)
+            .copied()
+            .collect()
+    }
+}
+
+/// The first `threshold` candidates after a stable sort, so ties keep index order
+fn rank_by(
+    candidates: &[usize],
+    threshold: usize,
+    compare: impl FnMut(&usize, &usize) -> Ordering,
+) -> Vec<usize> {
+    let mut ranked = candidates.to_vec();
+    ranked.sort_by(compare);
+    ranked.truncate(threshold);
+    ranked
+}
@@ -1,29 +1,107 @@
 //! ROAST Signer
 //!
 //! Manage a FROST key in order to send nonces and signature shares upon request from a ROAST coordinator.
-use rand::RngCore;
-use secp256kfun::{
-    marker::{Public, Zero},
-    Scalar,
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
 };
 
-use schnorr_fun::{
-    musig::{Nonce, NonceKeyPair},
-    Message,
-};
+use rand::RngCore;
 
-use crate::threshold_scheme::ThresholdScheme;
+use crate::{
+    announcement::{
+        AnnouncementScheme, EquivocationProof, SchemeAnnouncement, SchemeEquivocationProof,
+        SchnorrAnnouncements, SessionAnnouncement,
+    },
+    protocol::{
+        message_id, CoordinatorMessage, MessageId, SchemeCoordinatorMessage, SchemeSignerMessage,
+        SignerMessage,
+    },
+    threshold_scheme::ThresholdScheme,
+};
 
-pub struct RoastSigner<'a, S: ThresholdScheme<K>, K: Clone> {
+pub struct RoastSigner<
+    S: ThresholdScheme<K>,
+    K: Clone,
+    T: AnnouncementScheme = SchnorrAnnouncements,
+> {
     threshold_scheme: S,
     joint_key: K,
     my_index: usize,
-    secret_share: Scalar,
-    message: Message<'a, Public>,
-    my_nonces: Vec<NonceKeyPair>,
+    secret_share: S::SecretShare,
+    message: S::Message,
+    message_id: MessageId,
+    // our unused nonces, along with their public nonces
+    my_nonces: Vec<(S::PublicNonce, S::SecretNonce)>,
+    // every nonce in the nonce sets we have signed
+    used_nonces: Vec<S::PublicNonce>,
+    signature: Option<S::Signature>,
+    announcement_scheme: T,
+    // when set, every sign request must be a signed announcement from this coordinator key
+    coordinator_key: Option<T::CoordinatorKey>,
+    announcements: HashMap<usize, SchemeAnnouncement<S, K, T>>,
+    equivocation: Option<SchemeEquivocationProof<S, K, T>>,
 }
 
-impl<'a, S: ThresholdScheme<K> + Clone, K: Clone> RoastSigner<'a, S, K> {
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SignerError {
+    /// The nonce set does not contain an unused nonce of ours, so we cannot sign with it
+    NoMatchingNonce,
+    /// The nonce set does not include us
+    NotInNonceSet,
+    /// The nonce set contains a signer index outside the group
+    UnknownSigner(usize),
+    /// The nonce set contains more than one nonce for a signer
+    DuplicateIndex(usize),
+    /// The nonce set has fewer nonces than the threshold
+    TooFewNonces(usize),
+    /// The sign request is for another message than the one we are signing
+    WrongMessage,
+    /// The nonce set reuses a signer's nonce from a nonce set we have already signed
+    ReusedNonce(usize),
+    /// A sign request or announcement was not validly signed by the coordinator
+    InvalidAnnouncement,
+    /// The coordinator announced different nonce sets for the same sign session, so we no longer
+    /// sign for it
+    CoordinatorEquivocated,
+}
+
+impl fmt::Display for SignerError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::NoMatchingNonce => write!(f, "No unused nonce of ours in the nonce set"),
+            Self::NotInNonceSet => write!(f, "We are not included in the nonce set"),
+            Self::UnknownSigner(index) => {
+                write!(f, "Nonce set contains unknown signer index {}", index)
+            }
+            Self::DuplicateIndex(index) => {
+                write!(f, "Nonce set contains signer {} more than once", index)
+            }
+            Self::TooFewNonces(n_nonces) => write!(
+                f,
+                "Nonce set contains {} nonces, fewer than the threshold",
+                n_nonces
+            ),
+            Self::WrongMessage => write!(f, "Sign request is for another message"),
+            Self::ReusedNonce(index) => write!(
+                f,
+                "Nonce set reuses a previously signed nonce of signer {}",
+                index
+            ),
+            Self::InvalidAnnouncement => {
+                write!(f, "Session announcement not signed by the coordinator")
+            }
+            Self::CoordinatorEquivocated => write!(
+                f,
+                "Coordinator announced conflicting nonce sets for a sign session"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SignerError {}
+
+impl<S: ThresholdScheme<K> + Clone, K: Clone> RoastSigner<S, K> {
     /// Create a new [`RoastSigner`] session for a particular message
     ///
     /// A new [`RoastSigner`] should be created for each message the group wants to sign.
@@ -32,16 +110,49 @@ impl<'a, S: ThresholdScheme<K> + Clone, K: Clone> RoastSigner<'a, S, K> {
     /// that nonces are never reused. See *[secp256kfun FROST]* for more info.
     ///
     /// [secp256kfun FROST]: <https://docs.rs/schnorr_fun/latest/schnorr_fun/frost/index.html>
+    ///
+    /// # Returns
+    ///
+    /// Returns the signer along with its initial [`SignerMessage::NonceOnly`] for the coordinator
     pub fn new(
         nonce_rng: &mut impl RngCore,
         threshold_scheme: S,
         joint_key: K,
         my_index: usize,
-        secret_share: Scalar,
-        message: Message<'a>,
-    ) -> (RoastSigner<'a, S, K>, Nonce) {
+        secret_share: S::SecretShare,
+        message: impl Into<S::Message>,
+    ) -> (RoastSigner<S, K>, SchemeSignerMessage<S, K>) {
+        Self::with_announcement_scheme(
+            nonce_rng,
+            threshold_scheme,
+            joint_key,
+            my_index,
+            secret_share,
+            message,
+            SchnorrAnnouncements,
+        )
+    }
+}
+
+impl<S: ThresholdScheme<K> + Clone, K: Clone, T: AnnouncementScheme> RoastSigner<S, K, T> {
+    /// Create a new [`RoastSigner`] which checks session announcements with `announcement_scheme`
+    ///
+    /// Announcements are only required once a key is given with
+    /// [`RoastSigner::with_coordinator_key`].
+    pub fn with_announcement_scheme(
+        nonce_rng: &mut impl RngCore,
+        threshold_scheme: S,
+        joint_key: K,
+        my_index: usize,
+        secret_share: S::SecretShare,
+        message: impl Into<S::Message>,
+        announcement_scheme: T,
+    ) -> (RoastSigner<S, K, T>, SchemeSignerMessage<S, K>) {
         let initial_nonce = threshold_scheme.gen_nonce(nonce_rng);
-        let my_nonces = vec![initial_nonce.clone()];
+        let public_nonce = threshold_scheme.public_nonce(&initial_nonce);
+        let my_nonces = vec![(public_nonce.clone(), initial_nonce)];
+        let message = message.into();
+        let message_id = message_id(&message);
 
         (
             RoastSigner {
@@ -50,42 +161,250 @@ impl<'a, S: ThresholdScheme<K> + Clone, K: Clone> RoastSigner<'a, S, K> {
                 my_index,
                 secret_share,
                 message,
+                message_id,
                 my_nonces,
+                used_nonces: vec![],
+                signature: None,
+                announcement_scheme,
+                coordinator_key: None,
+                announcements: HashMap::new(),
+                equivocation: None,
+            },
+            SignerMessage::NonceOnly {
+                message_id,
+                nonce: public_nonce,
             },
-            initial_nonce.public(),
         )
     }
 
-    /// Create a new nonce using the [`Frost`]'s internal noncegen
-    pub fn new_nonce(&mut self, nonce_rng: &mut impl RngCore) -> NonceKeyPair {
+    /// The [`message_id`] of the message we are signing
+    pub fn message_id(&self) -> MessageId {
+        self.message_id
+    }
+
+    /// Create a new nonce using the threshold scheme's nonce generation
+    ///
+    /// The secret nonce is stored until a nonce set containing its public nonce is signed.
+    ///
+    /// # Returns
+    ///
+    /// Returns the public nonce to share with the coordinator
+    pub fn new_nonce(&mut self, nonce_rng: &mut impl RngCore) -> S::PublicNonce {
         let nonce = self.threshold_scheme.gen_nonce(nonce_rng);
-        self.my_nonces.push(nonce.clone());
-        nonce
+        let public_nonce = self.threshold_scheme.public_nonce(&nonce);
+        self.my_nonces.push((public_nonce.clone(), nonce));
+        public_nonce
     }
 
-    /// Sign the message with a nonce set
+    /// Check a nonce set received from the coordinator is safe for us to sign with
     ///
-    /// Also generates a new nonce to share and use for the next signing round
-    pub fn sign(
+    /// The nonce set must be a well formed set of at least threshold signers which includes us
+    /// with one of our unused nonces, and must not reuse any nonce from a nonce set we have
+    /// already signed.
+    pub fn validate_nonce_set(
+        &self,
+        nonce_set: &[(usize, S::PublicNonce)],
+    ) -> Result<(), SignerError> {
+        let n_signers = self.threshold_scheme.n_signers(&self.joint_key);
+        let mut indices = HashSet::new();
+        for (index, _) in nonce_set {
+            if *index >= n_signers {
+                return Err(SignerError::UnknownSigner(*index));
+            }
+            if !indices.insert(*index) {
+                return Err(SignerError::DuplicateIndex(*index));
+            }
+        }
+        if nonce_set.len() < self.threshold_scheme.threshold(&self.joint_key) {
+            return Err(SignerError::TooFewNonces(nonce_set.len()));
+        }
+        let (_, my_nonce) = nonce_set
+            .iter()
+            .find(|(index, _)| *index == self.my_index)
+            .ok_or(SignerError::NotInNonceSet)?;
+        if !self.my_nonces.iter().any(|(public, _)| public == my_nonce) {
+            return Err(SignerError::NoMatchingNonce);
+        }
+        if let Some((index, _)) = nonce_set
+            .iter()
+            .find(|(_, nonce)| self.used_nonces.contains(nonce))
+        {
+            return Err(SignerError::ReusedNonce(*index));
+        }
+        Ok(())
+    }
+
+    /// Sign the message with the nonce set of a sign session
+    ///
+    /// The nonce set is first checked with [`RoastSigner::validate_nonce_set`]. The secret nonce
+    /// matching our entry in the nonce set is erased once used, so each nonce can only ever sign
+    /// once. Also generates a new nonce to share and use for the next signing round.
+    ///
+    /// Only reached through [`RoastSigner::receive`], once the sign request has passed the message
+    /// id, equivocation and announcement checks.
+    fn sign(
         &mut self,
         nonce_rng: &mut impl RngCore,
-        nonce_set: Vec<(usize, Nonce)>,
-    ) -> (Scalar<Public, Zero>, Nonce) {
-        // call server with (sig, self.new_nonce())
-        let my_nonce = self
+        session_id: usize,
+        nonce_set: Vec<(usize, S::PublicNonce)>,
+    ) -> Result<SchemeSignerMessage<S, K>, SignerError> {
+        self.validate_nonce_set(&nonce_set)?;
+        let my_public_nonce = nonce_set
+            .iter()
+            .find(|(index, _)| *index == self.my_index)
+            .map(|(_, nonce)| nonce)
+            .expect("validated nonce set includes us");
+        let position = self
             .my_nonces
-            .pop()
-            .expect("some nonce available for signing");
-        let sig = self.threshold_scheme.sign(
-            self.joint_key.clone(),
-            nonce_set,
+            .iter()
+            .position(|(public, _)| public == my_public_nonce)
+            .expect("validated nonce set has our unused nonce");
+        let (_, my_nonce) = self.my_nonces.remove(position);
+        self.used_nonces
+            .extend(nonce_set.iter().map(|(_, nonce)| nonce.clone()));
+        let session =
+            self.threshold_scheme
+                .start_sign_session(&self.joint_key, nonce_set, &self.message);
+        let signature_share = self.threshold_scheme.sign(
+            &self.joint_key,
+            &session,
             self.my_index,
             &self.secret_share,
             my_nonce,
-            self.message,
         );
         // Must be called **after sign**
         let nonce = self.new_nonce(nonce_rng);
-        (sig, nonce.public())
+        Ok(SignerMessage::ShareAndNonce {
+            message_id: self.message_id,
+            session_id,
+            signature_share,
+            nonce,
+        })
+    }
+
+    /// Receive a message from the coordinator
+    ///
+    /// # Returns
+    ///
+    /// Returns the [`SignerMessage`] to reply to the coordinator with, if any
+    pub fn receive(
+        &mut self,
+        nonce_rng: &mut impl RngCore,
+        message: SchemeCoordinatorMessage<S, K, T>,
+    ) -> Result<Option<SchemeSignerMessage<S, K>>, SignerError> {
+        match message {
+            CoordinatorMessage::SignRequest {
+                message_id,
+                session_id,
+                nonce_set,
+                announcement_signature,
+            } => {
+                if message_id != self.message_id {
+                    return Err(SignerError::WrongMessage);
+                }
+                if self.equivocation.is_some() {
+                    return Err(SignerError::CoordinatorEquivocated);
+                }
+                if self.coordinator_key.is_some() {
+                    let signature =
+                        announcement_signature.ok_or(SignerError::InvalidAnnouncement)?;
+                    self.check_announcement(SessionAnnouncement {
+                        message: self.message.clone(),
+                        session_id,
+                        nonce_set: nonce_set.clone(),
+                        signature,
+                    })?;
+                }
+                self.sign(nonce_rng, session_id, nonce_set).map(Some)
+            }
+            CoordinatorMessage::Completed { signature } => {
+                if self.threshold_scheme.verify_signature(
+                    &self.joint_key,
+                    &self.message,
+                    &signature,
+                ) {
+                    self.signature = Some(signature);
+                    // The message is signed, so our nonces will never be needed
+                    self.my_nonces.clear();
+                }
+                Ok(None)
+            }
+            CoordinatorMessage::Banned => Ok(None),
+        }
+    }
+
+    /// Require every sign request to be signed by the coordinator's announcement key
+    ///
+    /// See [`crate::announcement`].
+    pub fn with_coordinator_key(mut self, coordinator_key: T::CoordinatorKey) -> Self {
+        self.coordinator_key = Some(coordinator_key);
+        self
+    }
+
+    /// Check a session announcement, either from a sign request or passed on by another signer
+    ///
+    /// Announcements for other messages are ignored.
+    ///
+    /// # Returns
+    ///
+    /// Returns [`SignerError::CoordinatorEquivocated`] if the coordinator has announced a different
+    /// nonce set for the same sign session, after which we refuse to sign for it. The evidence is
+    /// kept as an [`EquivocationProof`].
+    pub fn check_announcement(
+        &mut self,
+        announcement: SchemeAnnouncement<S, K, T>,
+    ) -> Result<(), SignerError> {
+        let coordinator_key = self
+            .coordinator_key
+            .as_ref()
+            .ok_or(SignerError::InvalidAnnouncement)?;
+        if !announcement.verify(&self.announcement_scheme, coordinator_key) {
+            return Err(SignerError::InvalidAnnouncement);
+        }
+        if announcement.message != self.message {
+            return Ok(());
+        }
+        match self.announcements.get(&announcement.session_id) {
+            Some(seen) if seen.conflicts_with(&announcement) => {
+                self.equivocation = Some(EquivocationProof {
+                    first: seen.clone(),
+                    second: announcement,
+                });
+                Err(SignerError::CoordinatorEquivocated)
+            }
+            Some(_) => Ok(()),
+            None => {
+                self.announcements
+                    .insert(announcement.session_id, announcement);
+                Ok(())
+            }
+        }
+    }
+
+    /// The session announcements we have seen, to be passed on to other signers
+    pub fn announcements(&self) -> Vec<SchemeAnnouncement<S, K, T>> {
+        self.announcements.values().cloned().collect()
+    }
+
+    /// Proof that the coordinator equivocated, if we have caught it
+    pub fn equivocation_proof(&self) -> Option<&SchemeEquivocationProof<S, K, T>> {
+        self.equivocation.as_ref()
+    }
+
+    /// The combined signature, once the coordinator has announced one which verifies
+    ///
+    /// Our unused nonces are discarded at the same time.
+    pub fn signature(&self) -> Option<S::Signature> {
+        self.signature.clone()
+    }
+
+    /// The message we are signing
+    pub fn message(&self) -> &S::Message {
+        &self.message
+    }
+
+    /// Our index in the group
+    pub fn index(&self) -> usize {
+        self.my_index
     }
 }
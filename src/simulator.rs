@@ -0,0 +1,359 @@
+//! ROAST Network Simulator
+//!
+//! Drives a [`Coordinator`] and its [`RoastSigner`]s through a deterministic discrete-event
+//! simulation. Every message travels over a [`Link`] with its own latency distribution, drop rate
+//! and ordering, against a virtual clock, so asynchronous networks can be replayed exactly from a
+//! seed.
+//!
+//! Messages are delivered once at most and never retransmitted, so a scenario which loses too many
+//! messages may end without a signature.
+//!
+//! Like a real coordinator on a timer, the simulated one calls [`Coordinator::open_session`]
+//! whenever it receives nothing for a while, so absent signers cannot stall a pool larger than the
+//! threshold.
+//!
+//! Signers can be made faulty or malicious with an [`Adversary`].
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::{
+    adversary::{Adversary, SchemeAdversary},
+    coordinator::{Coordinator, RoastError, SchemeResponse},
+    link::Link,
+    observer::CoordinatorEvent,
+    protocol::{CoordinatorMessage, SchemeCoordinatorMessage, SchemeSignerMessage, SignerMessage},
+    signer::RoastSigner,
+    threshold_scheme::ThresholdScheme,
+};
+
+/// What happened during a simulated run
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report<Sig> {
+    /// The combined signature, if the message was signed
+    pub signature: Option<Sig>,
+    /// The virtual time at which the coordinator combined the signature
+    pub time_to_signature: Option<u64>,
+    /// Number of sign sessions the coordinator opened
+    pub sessions_opened: usize,
+    /// Number of messages sent, counting one per recipient
+    pub messages_sent: usize,
+    /// Number of sent messages which were lost
+    pub messages_dropped: usize,
+}
+
+enum Envelope<S: ThresholdScheme<K>, K> {
+    ToCoordinator(usize, SchemeSignerMessage<S, K>),
+    ToSigner(usize, SchemeCoordinatorMessage<S, K>),
+}
+
+/// A simulated run of a coordinator and its signers
+///
+/// The simulation takes the coordinator, observing it to count the sign sessions it opens.
+/// [`Coordinator::start_session`] must have been called before running. Signers are given along
+/// with their initial message, as returned by [`RoastSigner::new`], and any signers of the group
+/// which are not given are treated as absent.
+pub struct Simulation<S: ThresholdScheme<K>, K: Clone> {
+    coordinator: Coordinator<S, K>,
+    sessions_opened: Arc<AtomicUsize>,
+    signers: HashMap<usize, RoastSigner<S, K>>,
+    initial_messages: Vec<(usize, SchemeSignerMessage<S, K>)>,
+    adversaries: HashMap<usize, SchemeAdversary<S, K>>,
+    first_nonces: HashMap<usize, S::PublicNonce>,
+    rng: StdRng,
+    default_link: Link,
+    // links to and from individual signers, overriding the default
+    uplinks: HashMap<usize, Link>,
+    downlinks: HashMap<usize, Link>,
+    time_limit: u64,
+    idle_timeout: u64,
+}
+
+impl<S: ThresholdScheme<K> + Clone, K: Clone> Simulation<S, K> {
+    /// Create a simulation where every link delivers in order after a single tick
+    ///
+    /// The `seed` determines every latency, drop and nonce in the run.
+    pub fn new(
+        coordinator: Coordinator<S, K>,
+        signers: Vec<(RoastSigner<S, K>, SchemeSignerMessage<S, K>)>,
+        seed: u64,
+    ) -> Self {
+        let sessions_opened = Arc::new(AtomicUsize::new(0));
+        let counter = sessions_opened.clone();
+        let coordinator =
+            coordinator.with_observer(move |event: &CoordinatorEvent<S::Signature>| {
+                if let CoordinatorEvent::SignSessionOpened { .. } = event {
+                    counter.fetch_add(1, Ordering::Relaxed);
+                }
+            });
+        let mut initial_messages = vec![];
+        let signers = signers
+            .into_iter()
+            .map(|(signer, initial_message)| {
+                initial_messages.push((signer.index(), initial_message));
+                (signer.index(), signer)
+            })
+            .collect();
+        Self {
+            coordinator,
+            sessions_opened,
+            signers,
+            initial_messages,
+            adversaries: HashMap::new(),
+            first_nonces: HashMap::new(),
+            rng: StdRng::seed_from_u64(seed),
+            default_link: Link::default(),
+            uplinks: HashMap::new(),
+            downlinks: HashMap::new(),
+            time_limit: u64::MAX,
+            idle_timeout: 1_000,
+        }
+    }
+
+    /// Use this link in both directions between the coordinator and every signer
+    pub fn with_link(mut self, link: Link) -> Self {
+        self.default_link = link;
+        self
+    }
+
+    /// Use these links from and to a particular signer
+    pub fn with_signer_links(mut self, index: usize, uplink: Link, downlink: Link) -> Self {
+        self.uplinks.insert(index, uplink);
+        self.downlinks.insert(index, downlink);
+        self
+    }
+
+    /// Have a signer behave as an [`Adversary`]
+    pub fn with_adversary(mut self, index: usize, adversary: SchemeAdversary<S, K>) -> Self {
+        self.adversaries.insert(index, adversary);
+        self
+    }
+
+    /// Stop the run once the virtual clock passes `time_limit`
+    pub fn with_time_limit(mut self, time_limit: u64) -> Self {
+        self.time_limit = time_limit;
+        self
+    }
+
+    /// Open a sign session with the responsive signers once the coordinator has received nothing
+    /// for `idle_timeout` ticks, by default 1000
+    pub fn with_idle_timeout(mut self, idle_timeout: u64) -> Self {
+        self.idle_timeout = idle_timeout;
+        self
+    }
+
+    /// Run until the message is signed, the coordinator gives up, no messages remain in flight, or
+    /// the time limit is reached
+    pub fn run(mut self) -> Report<S::Signature> {
+        let mut network: Network<S, K> = Network {
+            queue: BTreeMap::new(),
+            sequence: 0,
+            link_clocks: HashMap::new(),
+            report: Report {
+                signature: None,
+                time_to_signature: None,
+                sessions_opened: 0,
+                messages_sent: 0,
+                messages_dropped: 0,
+            },
+        };
+        for (index, message) in std::mem::take(&mut self.initial_messages) {
+            self.first_nonces.insert(index, message.nonce());
+            if let Some(false) = self
+                .adversaries
+                .get(&index)
+                .map(Adversary::sends_first_nonce)
+            {
+                continue;
+            }
+            self.send_to_coordinator(&mut network, 0, index, message);
+        }
+
+        let mut last_received: u64 = 0;
+        loop {
+            let next = network.queue.first_key_value().map(|((time, _), _)| *time);
+            let idle_at = last_received.saturating_add(self.idle_timeout);
+            // Nothing reaches the coordinator for a while, so stop waiting on the rest of the pool
+            if next.is_none_or(|time| time > idle_at) {
+                if idle_at > self.time_limit {
+                    break;
+                }
+                last_received = idle_at;
+                match self.coordinator.open_session() {
+                    Ok(Some(response)) => {
+                        if self.respond(&mut network, idle_at, response) {
+                            break;
+                        }
+                        continue;
+                    }
+                    Ok(None) if next.is_some() => {}
+                    _ => break,
+                }
+            }
+            let ((now, _), envelope) = network.queue.pop_first().expect("a message is in flight");
+            if now > self.time_limit {
+                break;
+            }
+            match envelope {
+                Envelope::ToCoordinator(index, message) => {
+                    last_received = now;
+                    let response = match self.coordinator.receive(index, message) {
+                        Ok(response) => response,
+                        Err(RoastError::InvalidSignature { .. }) => {
+                            match self.coordinator.open_session() {
+                                Ok(Some(response)) => response,
+                                Ok(None) => continue,
+                                Err(_) => break,
+                            }
+                        }
+                        Err(RoastError::TooFewHonest | RoastError::Storage(_)) => break,
+                        // Malformed messages leave the coordinator state untouched
+                        Err(_) => continue,
+                    };
+                    if self.respond(&mut network, now, response) {
+                        break;
+                    }
+                }
+                Envelope::ToSigner(index, message) => {
+                    if let Some(false) = self.adversaries.get(&index).map(Adversary::replies) {
+                        continue;
+                    }
+                    let signer = match self.signers.get_mut(&index) {
+                        Some(signer) => signer,
+                        None => continue,
+                    };
+                    // A signer refusing an unsafe nonce set simply does not reply
+                    if let Ok(Some(reply)) = signer.receive(&mut self.rng, message) {
+                        self.send_to_coordinator(&mut network, now, index, reply);
+                    }
+                }
+            }
+        }
+        network.report.sessions_opened = self.sessions_opened.load(Ordering::Relaxed);
+        network.report
+    }
+
+    /// Send the coordinator's response to its recipients
+    ///
+    /// Returns whether the response completes the run.
+    fn respond(
+        &mut self,
+        network: &mut Network<S, K>,
+        now: u64,
+        response: SchemeResponse<S, K>,
+    ) -> bool {
+        let message = match response.message {
+            Some(message) => message,
+            None => return false,
+        };
+        if let CoordinatorMessage::Completed { signature } = &message {
+            network.report.signature = Some(signature.clone());
+            network.report.time_to_signature = Some(now);
+            // The run ends here, so the signature is counted as sent but never delivered
+            network.report.messages_sent += response.recipients.len();
+            return true;
+        }
+        // Recipients are sent to in index order, keeping the run deterministic
+        let mut recipients = response.recipients;
+        recipients.sort_unstable();
+        for recipient in recipients {
+            let link = self.downlink(recipient);
+            network.send(
+                &mut self.rng,
+                now,
+                (recipient, false),
+                link,
+                Envelope::ToSigner(recipient, message.clone()),
+            );
+        }
+        false
+    }
+
+    /// Send a signer's message, as tampered with by its adversary if it has one
+    fn send_to_coordinator(
+        &mut self,
+        network: &mut Network<S, K>,
+        now: u64,
+        index: usize,
+        message: SchemeSignerMessage<S, K>,
+    ) {
+        let link = self.uplink(index);
+        let adversary = match self.adversaries.get(&index) {
+            Some(adversary) => adversary,
+            None => {
+                let envelope = Envelope::ToCoordinator(index, message);
+                network.send(&mut self.rng, now, (index, true), link, envelope);
+                return;
+            }
+        };
+        let first_nonce = &self.first_nonces[&index];
+        let message_id = message.message_id();
+        let message = adversary.tamper(message, first_nonce);
+        let sent_at = now.saturating_add(adversary.delay());
+        let unsolicited = matches!(adversary, Adversary::Unsolicited);
+        let envelope = Envelope::ToCoordinator(index, message);
+        network.send(&mut self.rng, sent_at, (index, true), link, envelope);
+        if unsolicited {
+            if let Some(signer) = self.signers.get_mut(&index) {
+                let nonce = signer.new_nonce(&mut self.rng);
+                let message = SignerMessage::NonceOnly { message_id, nonce };
+                let envelope = Envelope::ToCoordinator(index, message);
+                network.send(&mut self.rng, sent_at, (index, true), link, envelope);
+            }
+        }
+    }
+
+    fn uplink(&self, index: usize) -> Link {
+        self.uplinks
+            .get(&index)
+            .copied()
+            .unwrap_or(self.default_link)
+    }
+
+    fn downlink(&self, index: usize) -> Link {
+        self.downlinks
+            .get(&index)
+            .copied()
+            .unwrap_or(self.default_link)
+    }
+}
+
+/// The messages in flight, ordered by delivery time and then by when they were sent
+struct Network<S: ThresholdScheme<K>, K> {
+    queue: BTreeMap<(u64, u64), Envelope<S, K>>,
+    sequence: u64,
+    // the latest delivery time on each link, keyed by signer and whether it leads to the coordinator
+    link_clocks: HashMap<(usize, bool), u64>,
+    report: Report<S::Signature>,
+}
+
+impl<S: ThresholdScheme<K>, K> Network<S, K> {
+    fn send(
+        &mut self,
+        rng: &mut StdRng,
+        now: u64,
+        link_id: (usize, bool),
+        link: Link,
+        envelope: Envelope<S, K>,
+    ) {
+        self.report.messages_sent += 1;
+        if link.drop_rate > 0.0 && rng.gen_bool(link.drop_rate.min(1.0)) {
+            self.report.messages_dropped += 1;
+            return;
+        }
+        let mut delivery = now.saturating_add(link.latency.sample(rng));
+        if !link.reorder {
+            let link_clock = self.link_clocks.entry(link_id).or_default();
+            delivery = delivery.max(*link_clock);
+            *link_clock = delivery;
+        }
+        self.queue.insert((delivery, self.sequence), envelope);
+        self.sequence += 1;
+    }
+}
@@ -0,0 +1,168 @@
+//! ROAST Coordinator Storage
+//!
+//! A [`StateStore`] durably keeps the [`RoastState`] of a [`Coordinator`] so that it can be restored
+//! after a restart, see [`Coordinator::with_store`].
+//!
+//! [`Coordinator`]: crate::coordinator::Coordinator
+//! [`Coordinator::with_store`]: crate::coordinator::Coordinator::with_store
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, BufRead, BufReader, Write},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::coordinator::RoastState;
+
+/// A durable home for a coordinator's [`RoastState`]
+///
+/// `T` is the state type of the coordinator's threshold scheme, see
+/// [`SchemeState`](crate::coordinator::SchemeState).
+pub trait StateStore<T = RoastState> {
+    /// Durably record the state, replacing any previously saved state
+    fn save(&mut self, state: &T) -> io::Result<()>;
+
+    /// The most recently saved state, if any
+    fn load(&mut self) -> io::Result<Option<T>>;
+}
+
+/// A [`StateStore`] kept in memory
+///
+/// Clones share the same state, so a clone can be kept to restore a new coordinator after the
+/// original is dropped.
+#[derive(Debug, Clone)]
+pub struct MemoryStore<T = RoastState> {
+    state: Arc<Mutex<Option<T>>>,
+}
+
+impl<T> MemoryStore<T> {
+    pub fn new() -> Self {
+        Self {
+            state: Arc::new(Mutex::new(None)),
+        }
+    }
+}
+
+impl<T> Default for MemoryStore<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone> StateStore<T> for MemoryStore<T> {
+    fn save(&mut self, state: &T) -> io::Result<()> {
+        *self.state.lock().expect("got lock") = Some(state.clone());
+        Ok(())
+    }
+
+    fn load(&mut self) -> io::Result<Option<T>> {
+        Ok(self.state.lock().expect("got lock").clone())
+    }
+}
+
+/// A [`StateStore`] backed by a log of state snapshots
+///
+/// Every saved state is appended to the log in full as a line of JSON and synced to disk before
+/// `save` returns. The state only records what the coordinator has received, never its responses,
+/// so each entry grows with the number of signers rather than with the messages exchanged.
+/// Loading takes the last complete entry, so a write torn by a crash falls back to the state before
+/// it. Once appending an entry would grow the log beyond `max_size` bytes, the log is instead
+/// atomically rewritten to hold only that entry, so it never holds more than one entry larger than
+/// `max_size`.
+#[derive(Debug)]
+pub struct FileStore {
+    path: PathBuf,
+    file: File,
+    size: u64,
+    max_size: u64,
+}
+
+impl FileStore {
+    /// Open the log at `path`, creating it if it does not exist
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let mut file = OpenOptions::new()
+            .create(true)
+            .read(true)
+            .append(true)
+            .open(&path)?;
+        let contents = fs::read(&path)?;
+        if contents.last().is_some_and(|last| *last != b'\n') {
+            // Terminate a torn write so the next entry starts on its own line
+            file.write_all(b"\n")?;
+            file.sync_data()?;
+        }
+        let size = file.metadata()?.len();
+        Ok(Self {
+            path,
+            file,
+            size,
+            max_size: 1 << 20,
+        })
+    }
+
+    /// Set how many bytes the log may grow to before it is compacted, 1 MiB by default
+    pub fn with_max_size(mut self, max_size: u64) -> Self {
+        self.max_size = max_size;
+        self
+    }
+
+    fn compact(&mut self, entry: &[u8]) -> io::Result<()> {
+        let tmp_path = self.path.with_extension("compacting");
+        let mut tmp = File::create(&tmp_path)?;
+        tmp.write_all(entry)?;
+        tmp.sync_all()?;
+        fs::rename(&tmp_path, &self.path)?;
+        // The rename is only durable once the directory holding the log is synced
+        sync_parent(&self.path)?;
+        self.file = OpenOptions::new()
+            .read(true)
+            .append(true)
+            .open(&self.path)?;
+        self.size = entry.len() as u64;
+        Ok(())
+    }
+}
+
+#[cfg(unix)]
+fn sync_parent(path: &Path) -> io::Result<()> {
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    File::open(parent)?.sync_all()
+}
+
+// Directories cannot be opened to be synced on other platforms
+#[cfg(not(unix))]
+fn sync_parent(_path: &Path) -> io::Result<()> {
+    Ok(())
+}
+
+impl<T: Serialize + DeserializeOwned> StateStore<T> for FileStore {
+    fn save(&mut self, state: &T) -> io::Result<()> {
+        let mut entry = serde_json::to_vec(state)?;
+        entry.push(b'\n');
+        if self.size + entry.len() as u64 > self.max_size {
+            return self.compact(&entry);
+        }
+        self.file.write_all(&entry)?;
+        self.file.sync_data()?;
+        self.size += entry.len() as u64;
+        Ok(())
+    }
+
+    fn load(&mut self) -> io::Result<Option<T>> {
+        let file = File::open(&self.path)?;
+        let mut state = None;
+        for line in BufReader::new(file).lines() {
+            // A torn final write leaves an entry which does not parse
+            if let Ok(entry) = serde_json::from_str(&line?) {
+                state = Some(entry);
+            }
+        }
+        Ok(state)
+    }
+}
@@ -1,43 +1,93 @@
+use std::fmt::Debug;
+
 use rand::RngCore;
-use schnorr_fun::{frost::Nonce, musig::NonceKeyPair, Message, Signature};
-use secp256kfun::{
-    marker::{Public, Zero},
-    Scalar,
-};
+use serde::{de::DeserializeOwned, Serialize};
 
 /// A Threshold Signature Scheme to be used with ROAST
+///
+/// ROAST only needs the scheme to be semi-interactive, with nonces that can be shared before the
+/// message is known and signature shares that can be checked individually. The types exchanged
+/// between signers and the coordinator are serde serializable so they can be sent over the wire
+/// and persisted.
+///
+/// The messages, state, errors and proofs of this crate are generic over the types they carry
+/// rather than over the scheme: the joint key `K`, public nonce `N`, signature share `Z`,
+/// signature `Sig`, message `M`, sign session `P`, and the announcement signature `A` of an
+/// [`AnnouncementScheme`]. Each defaults to its FROST type, and a `Scheme*` alias such as
+/// [`SchemeSignerMessage`] fills them in from a scheme `S`.
+///
+/// [`AnnouncementScheme`]: crate::announcement::AnnouncementScheme
+/// [`SchemeSignerMessage`]: crate::protocol::SchemeSignerMessage
 pub trait ThresholdScheme<K> {
+    /// A signer's nonce as shared with the coordinator and the other signers
+    type PublicNonce: Clone + PartialEq + Debug + Serialize + DeserializeOwned;
+    /// The secret nonce kept by a signer until it signs with its public nonce
+    type SecretNonce;
+    /// A signer's share of the joint secret key
+    type SecretShare: Clone;
+    /// A signer's share of a signature, sent to the coordinator
+    type SignatureShare: Clone + PartialEq + Debug + Serialize + DeserializeOwned;
+    /// A signature under the joint key, combined from signature shares
+    type Signature: Clone + PartialEq + Debug + Serialize + DeserializeOwned;
+    /// A message to be signed
+    type Message: Clone + PartialEq + Debug + Serialize + DeserializeOwned;
+    /// The per-session values derived from a nonce set and message, such as binding coefficients
+    /// and the aggregate nonce, prepared once and reused for every signature share of the session
+    type SignSession: Clone;
+
+    /// The number of signers required to sign under the joint key
+    fn threshold(&self, joint_key: &K) -> usize;
+
+    /// The total number of signers holding shares of the joint key
+    fn n_signers(&self, joint_key: &K) -> usize;
+
     /// The scheme must implement a way for signers to generate nonces
-    fn gen_nonce<R: RngCore>(&self, nonce_rng: &mut R) -> NonceKeyPair;
+    fn gen_nonce<R: RngCore>(&self, nonce_rng: &mut R) -> Self::SecretNonce;
+
+    /// The public nonce to share for a secret nonce
+    fn public_nonce(&self, secret_nonce: &Self::SecretNonce) -> Self::PublicNonce;
+
+    /// Prepare the sign session for signing the message under a nonce set
+    fn start_sign_session(
+        &self,
+        joint_key: &K,
+        nonces: Vec<(usize, Self::PublicNonce)>,
+        message: &Self::Message,
+    ) -> Self::SignSession;
 
     /// The scheme must implement a way for signers to sign signature shares
     fn sign(
         &self,
-        joint_key: K,
-        nonces: Vec<(usize, Nonce)>,
+        joint_key: &K,
+        session: &Self::SignSession,
         my_index: usize,
-        secret_share: &Scalar,
-        secret_nonce: NonceKeyPair,
-        message: Message,
-    ) -> Scalar<Public, Zero>;
+        secret_share: &Self::SecretShare,
+        secret_nonce: Self::SecretNonce,
+    ) -> Self::SignatureShare;
 
     /// The scheme must implement identifiable aborts, if signing session fails then the coordinator
     /// can identify at least one malicious signer responsible for the failure.
     fn verify_signature_share(
         &self,
-        joint_key: K,
-        nonces: Vec<(usize, Nonce)>,
+        joint_key: &K,
+        session: &Self::SignSession,
         index: usize,
-        signature_share: Scalar<Public, Zero>,
-        message: Message,
+        signature_share: Self::SignatureShare,
     ) -> bool;
 
     /// The scheme must implement some way for coordinator to combine signature shares.
     fn combine_signature_shares(
         &self,
-        joint_key: K,
-        nonces: Vec<(usize, Nonce)>,
-        signature_shares: Vec<Scalar<Public, Zero>>,
-        message: Message,
-    ) -> Signature;
+        joint_key: &K,
+        session: &Self::SignSession,
+        signature_shares: Vec<Self::SignatureShare>,
+    ) -> Self::Signature;
+
+    /// The scheme must implement a way to check a combined signature under the joint key
+    fn verify_signature(
+        &self,
+        joint_key: &K,
+        message: &Self::Message,
+        signature: &Self::Signature,
+    ) -> bool;
 }
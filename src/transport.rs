@@ -0,0 +1,192 @@
+//! ROAST Transports
+//!
+//! A [`Transport`] carries protocol messages between the coordinator and its signers. Wrapping one
+//! in a [`FaultyTransport`] drops, delays, duplicates, reorders or partitions its messages
+//! according to a seeded schedule, so network-level failures can be reproduced in tests.
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::link::Link;
+
+/// Carries messages to or from the signer at `peer`
+pub trait Transport<M> {
+    fn send(&mut self, peer: usize, message: M);
+}
+
+/// Collects sent messages along with their peer, to be read back by the caller
+impl<M> Transport<M> for Vec<(usize, M)> {
+    fn send(&mut self, peer: usize, message: M) {
+        self.push((peer, message));
+    }
+}
+
+/// Delivers to a single receiver, tagging each message with its peer, e.g. the coordinator's inbox
+#[cfg(feature = "async")]
+impl<M> Transport<M> for tokio::sync::mpsc::UnboundedSender<(usize, M)> {
+    fn send(&mut self, peer: usize, message: M) {
+        // A receiver which has stopped has no need for the message
+        let _ = tokio::sync::mpsc::UnboundedSender::send(self, (peer, message));
+    }
+}
+
+/// Delivers to a receiver for each peer, e.g. the signers' inboxes
+#[cfg(feature = "async")]
+impl<M> Transport<M> for HashMap<usize, tokio::sync::mpsc::UnboundedSender<M>> {
+    fn send(&mut self, peer: usize, message: M) {
+        if let Some(outbox) = self.get(&peer) {
+            let _ = outbox.send(message);
+        }
+    }
+}
+
+/// A span of time during which every message to or from some peers is lost
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Partition {
+    pub peers: HashSet<usize>,
+    /// The first tick of the partition
+    pub start: u64,
+    /// The tick at which the partition heals
+    pub end: u64,
+}
+
+impl Partition {
+    fn isolates(&self, peer: usize, now: u64) -> bool {
+        self.start <= now && now < self.end && self.peers.contains(&peer)
+    }
+}
+
+/// Wraps a [`Transport`], injecting faults into every message sent over it
+///
+/// Time is counted in ticks which only pass when [`FaultyTransport::advance`] is called, and a
+/// message is handed to the inner transport once its delay has passed. Each message travels over
+/// the [`Link`] for its peer, and every fault is drawn from the seed, so a schedule can be replayed
+/// exactly.
+pub struct FaultyTransport<T, M> {
+    inner: T,
+    default_link: Link,
+    links: HashMap<usize, Link>,
+    duplicate_rate: f64,
+    partitions: Vec<Partition>,
+    rng: StdRng,
+    now: u64,
+    queue: BTreeMap<(u64, u64), (usize, M)>,
+    sequence: u64,
+    // the latest delivery time to or from each peer, keeping links without reordering in order
+    link_clocks: HashMap<usize, u64>,
+}
+
+impl<T: Transport<M>, M: Clone> FaultyTransport<T, M> {
+    /// Wrap a transport, sending every message over `link`
+    pub fn new(inner: T, link: Link, seed: u64) -> Self {
+        Self {
+            inner,
+            default_link: link,
+            links: HashMap::new(),
+            duplicate_rate: 0.0,
+            partitions: vec![],
+            rng: StdRng::seed_from_u64(seed),
+            now: 0,
+            queue: BTreeMap::new(),
+            sequence: 0,
+            link_clocks: HashMap::new(),
+        }
+    }
+
+    /// Send messages to or from a particular peer over a different link
+    pub fn with_peer_link(mut self, peer: usize, link: Link) -> Self {
+        self.links.insert(peer, link);
+        self
+    }
+
+    /// Deliver a second copy of a message with this probability, each copy delayed independently
+    pub fn with_duplicate_rate(mut self, duplicate_rate: f64) -> Self {
+        self.duplicate_rate = duplicate_rate;
+        self
+    }
+
+    /// Lose every message to or from these peers from `start` until the partition heals at `end`
+    ///
+    /// Messages already in flight when the partition starts are lost too.
+    pub fn with_partition(
+        mut self,
+        peers: impl IntoIterator<Item = usize>,
+        start: u64,
+        end: u64,
+    ) -> Self {
+        self.partitions.push(Partition {
+            peers: peers.into_iter().collect(),
+            start,
+            end,
+        });
+        self
+    }
+
+    /// The current tick
+    pub fn now(&self) -> u64 {
+        self.now
+    }
+
+    /// The number of messages in flight
+    pub fn in_flight(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Move time forward, handing every message whose delay has passed to the inner transport
+    pub fn advance(&mut self, ticks: u64) {
+        self.now = self.now.saturating_add(ticks);
+        while let Some(entry) = self.queue.first_entry() {
+            let (delivery, _) = *entry.key();
+            if delivery > self.now {
+                break;
+            }
+            let (peer, message) = entry.remove();
+            if !self.isolated(peer, delivery) {
+                self.inner.send(peer, message);
+            }
+        }
+    }
+
+    /// The wrapped transport
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// The wrapped transport, e.g. to read back the messages delivered to a collecting transport
+    pub fn inner_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    fn isolated(&self, peer: usize, now: u64) -> bool {
+        self.partitions
+            .iter()
+            .any(|partition| partition.isolates(peer, now))
+    }
+
+    fn schedule(&mut self, peer: usize, link: Link, message: M) {
+        let mut delivery = self.now.saturating_add(link.latency.sample(&mut self.rng));
+        if !link.reorder {
+            let link_clock = self.link_clocks.entry(peer).or_default();
+            delivery = delivery.max(*link_clock);
+            *link_clock = delivery;
+        }
+        self.queue
+            .insert((delivery, self.sequence), (peer, message));
+        self.sequence += 1;
+    }
+}
+
+impl<T: Transport<M>, M: Clone> Transport<M> for FaultyTransport<T, M> {
+    fn send(&mut self, peer: usize, message: M) {
+        let link = self.links.get(&peer).copied().unwrap_or(self.default_link);
+        if self.isolated(peer, self.now)
+            || (link.drop_rate > 0.0 && self.rng.gen_bool(link.drop_rate.min(1.0)))
+        {
+            return;
+        }
+        if self.duplicate_rate > 0.0 && self.rng.gen_bool(self.duplicate_rate.min(1.0)) {
+            self.schedule(peer, link, message.clone());
+        }
+        self.schedule(peer, link, message);
+    }
+}
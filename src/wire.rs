@@ -0,0 +1,211 @@
+//! ROAST Wire Format
+//!
+//! The framing used by the `roast-coordinator` and `roast-signer` binaries. Every frame is a JSON
+//! document prefixed by its length as a big-endian `u32`, sent over a TCP or Unix socket.
+//!
+//! A signer opens a connection with [`SignerFrame::Hello`], after which the coordinator announces
+//! each message to be signed with [`CoordinatorFrame::Sign`] and both sides exchange protocol
+//! messages. Signers are not authenticated, so the binaries are only suited to trusted networks.
+use std::{
+    fmt,
+    io::{self, Read, Write},
+    net::{Shutdown, TcpListener, TcpStream},
+    str::FromStr,
+};
+
+#[cfg(unix)]
+use std::{
+    os::unix::net::{UnixListener, UnixStream},
+    path::PathBuf,
+};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{
+    coordinator::StoredMessage,
+    protocol::{CoordinatorMessage, SignerMessage},
+};
+
+/// The largest frame we are willing to read
+pub const MAX_FRAME_LEN: usize = 1 << 24;
+
+/// A frame sent from a signer to the coordinator
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[allow(clippy::large_enum_variant)] // frames are short-lived
+pub enum SignerFrame {
+    /// Identify the connection as belonging to the signer at this index
+    Hello { index: usize },
+    /// A protocol message for the message being signed
+    Message(SignerMessage),
+}
+
+/// A frame sent from the coordinator to a signer
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[allow(clippy::large_enum_variant)] // frames are short-lived
+pub enum CoordinatorFrame {
+    /// Start signing a new message, abandoning any previous one
+    Sign { message: StoredMessage },
+    /// A protocol message for the message currently being signed
+    Message(CoordinatorMessage),
+    /// The signer's last protocol message was rejected as the coordinator holds no nonce of theirs,
+    /// e.g. after restarting without its state, so the signer should send a fresh nonce
+    Rejected,
+}
+
+/// Write a length-prefixed JSON frame
+pub fn write_frame<T: Serialize>(writer: &mut impl Write, frame: &T) -> io::Result<()> {
+    let body = serde_json::to_vec(frame)?;
+    if body.len() > MAX_FRAME_LEN {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "frame is too large",
+        ));
+    }
+    writer.write_all(&(body.len() as u32).to_be_bytes())?;
+    writer.write_all(&body)?;
+    writer.flush()
+}
+
+/// Read a length-prefixed JSON frame
+pub fn read_frame<T: DeserializeOwned>(reader: &mut impl Read) -> io::Result<T> {
+    let mut len = [0u8; 4];
+    reader.read_exact(&mut len)?;
+    let len = u32::from_be_bytes(len) as usize;
+    if len > MAX_FRAME_LEN {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "frame is too large",
+        ));
+    }
+    let mut body = vec![0u8; len];
+    reader.read_exact(&mut body)?;
+    Ok(serde_json::from_slice(&body)?)
+}
+
+/// A socket address, written as `tcp:<host>:<port>` or `unix:<path>`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Endpoint {
+    Tcp(String),
+    #[cfg(unix)]
+    Unix(PathBuf),
+}
+
+impl FromStr for Endpoint {
+    type Err = io::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(addr) = s.strip_prefix("tcp:") {
+            return Ok(Endpoint::Tcp(addr.to_string()));
+        }
+        #[cfg(unix)]
+        if let Some(path) = s.strip_prefix("unix:") {
+            return Ok(Endpoint::Unix(path.into()));
+        }
+        Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("unsupported endpoint {}", s),
+        ))
+    }
+}
+
+impl fmt::Display for Endpoint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Endpoint::Tcp(addr) => write!(f, "tcp:{}", addr),
+            #[cfg(unix)]
+            Endpoint::Unix(path) => write!(f, "unix:{}", path.display()),
+        }
+    }
+}
+
+impl Endpoint {
+    /// Listen for connections on the endpoint
+    pub fn bind(&self) -> io::Result<Listener> {
+        Ok(match self {
+            Endpoint::Tcp(addr) => Listener::Tcp(TcpListener::bind(addr)?),
+            #[cfg(unix)]
+            Endpoint::Unix(path) => Listener::Unix(UnixListener::bind(path)?),
+        })
+    }
+
+    /// Connect to a listener on the endpoint
+    pub fn connect(&self) -> io::Result<Stream> {
+        Ok(match self {
+            Endpoint::Tcp(addr) => Stream::Tcp(TcpStream::connect(addr)?),
+            #[cfg(unix)]
+            Endpoint::Unix(path) => Stream::Unix(UnixStream::connect(path)?),
+        })
+    }
+}
+
+pub enum Listener {
+    Tcp(TcpListener),
+    #[cfg(unix)]
+    Unix(UnixListener),
+}
+
+impl Listener {
+    /// Wait for the next connection
+    pub fn accept(&self) -> io::Result<Stream> {
+        Ok(match self {
+            Listener::Tcp(listener) => Stream::Tcp(listener.accept()?.0),
+            #[cfg(unix)]
+            Listener::Unix(listener) => Stream::Unix(listener.accept()?.0),
+        })
+    }
+}
+
+#[derive(Debug)]
+pub enum Stream {
+    Tcp(TcpStream),
+    #[cfg(unix)]
+    Unix(UnixStream),
+}
+
+impl Stream {
+    /// A second handle to the same connection, e.g. for writing while another thread reads
+    pub fn try_clone(&self) -> io::Result<Self> {
+        Ok(match self {
+            Stream::Tcp(stream) => Stream::Tcp(stream.try_clone()?),
+            #[cfg(unix)]
+            Stream::Unix(stream) => Stream::Unix(stream.try_clone()?),
+        })
+    }
+
+    /// Close the connection in both directions, ending any read blocked on another handle to it
+    pub fn shutdown(&self) -> io::Result<()> {
+        match self {
+            Stream::Tcp(stream) => stream.shutdown(Shutdown::Both),
+            #[cfg(unix)]
+            Stream::Unix(stream) => stream.shutdown(Shutdown::Both),
+        }
+    }
+}
+
+impl Read for Stream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            Stream::Tcp(stream) => stream.read(buf),
+            #[cfg(unix)]
+            Stream::Unix(stream) => stream.read(buf),
+        }
+    }
+}
+
+impl Write for Stream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Stream::Tcp(stream) => stream.write(buf),
+            #[cfg(unix)]
+            Stream::Unix(stream) => stream.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Stream::Tcp(stream) => stream.flush(),
+            #[cfg(unix)]
+            Stream::Unix(stream) => stream.flush(),
+        }
+    }
+}
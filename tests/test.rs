@@ -1,11 +1,15 @@
 #[cfg(feature = "frost")]
 mod tests {
+    use std::sync::{Arc, Mutex};
+
     use rand::seq::SliceRandom;
+    use rand::RngCore;
 
-    use schnorr_fun::frost as secp_frost;
-    use schnorr_fun::musig::Nonce;
+    use schnorr_fun::frost::{self as secp_frost, FrostKey};
+    use schnorr_fun::musig::NonceKeyPair;
     use schnorr_fun::nonce::Deterministic;
     use schnorr_fun::Message;
+    use secp256kfun::marker::EvenY;
     use secp256kfun::proptest::test_runner::RngAlgorithm;
     use secp256kfun::proptest::test_runner::TestRng;
     use secp256kfun::Scalar;
@@ -16,80 +20,2548 @@ mod tests {
         strategy::{Just, Strategy},
     };
 
-    use roast::coordinator;
-    use roast::signer;
+    use roast::blame::{verify_blame_proof, BlameProof};
+    use roast::coordinator::{self, RoastError};
+    use roast::observer::{BanReason, CoordinatorEvent};
+    use roast::protocol::{CoordinatorMessage, SchemeSignerMessage, SignerMessage};
+    use roast::signer::{self, SignerError};
+    use roast::storage::{FileStore, MemoryStore, StateStore};
+
+    type TestFrost = secp_frost::Frost<Sha256, Deterministic<Sha256>>;
+    type TestCoordinator = coordinator::Coordinator<TestFrost, FrostKey<EvenY>>;
+    type TestSigner = signer::RoastSigner<TestFrost, FrostKey<EvenY>>;
+    type TestSignerMessage = SchemeSignerMessage<TestFrost, FrostKey<EvenY>>;
+
+    /// A FROST group with a share of its joint key for every signer
+    struct Group {
+        frost: TestFrost,
+        joint_key: FrostKey<EvenY>,
+        secret_shares: Vec<Scalar>,
+    }
+
+    impl Group {
+        fn new(threshold: usize, n_signers: usize, rng: &mut impl RngCore) -> Self {
+            let frost = TestFrost::default();
+            let (frost_key, secret_shares) = frost.simulate_keygen(threshold, n_signers, rng);
+            Group {
+                frost,
+                joint_key: frost_key.into_xonly_key(),
+                secret_shares,
+            }
+        }
+
+        /// A coordinator for the group which has not started signing
+        fn coordinator(&self) -> TestCoordinator {
+            coordinator::Coordinator::new(self.frost.clone(), self.joint_key.clone())
+        }
+
+        /// Signers at `indices` for `message`, along with the first nonce each sends
+        fn signers(
+            &self,
+            indices: impl IntoIterator<Item = usize>,
+            message: Message,
+            rng: &mut impl RngCore,
+        ) -> (Vec<TestSigner>, Vec<TestSignerMessage>) {
+            indices
+                .into_iter()
+                .map(|i| {
+                    signer::RoastSigner::new(
+                        rng,
+                        self.frost.clone(),
+                        self.joint_key.clone(),
+                        i,
+                        self.secret_shares[i].clone(),
+                        message,
+                    )
+                })
+                .unzip()
+        }
+    }
+
+    /// A `threshold`-of-`n_signers` group, a coordinator signing `message`, and a signer for
+    /// every member along with its first nonce
+    fn setup(
+        threshold: usize,
+        n_signers: usize,
+        message: Message,
+        rng: &mut impl RngCore,
+    ) -> (
+        Group,
+        TestCoordinator,
+        Vec<TestSigner>,
+        Vec<TestSignerMessage>,
+    ) {
+        let group = Group::new(threshold, n_signers, rng);
+        let roast = group.coordinator();
+        roast.start_session(message).unwrap();
+        let (signers, nonces) = group.signers(0..n_signers, message, rng);
+        (group, roast, signers, nonces)
+    }
 
     #[test]
     fn test_2_of_3_basic() {
-        let frost = secp_frost::Frost::<Sha256, Deterministic<Sha256>>::default();
         let mut rng = rand::thread_rng();
-
-        let (frost_key, secret_shares) = frost.simulate_keygen(2, 3, &mut rng);
-        let xonly_frost_key = frost_key.into_xonly_key();
-
         let message = Message::plain("test", b"test");
-        let roast =
-            coordinator::Coordinator::new(frost.clone(), xonly_frost_key.clone(), message, 2, 3);
 
         // Create each signer session and create an initial nonce
-        let (mut signer1, nonce1) = signer::RoastSigner::new(
-            &mut rng,
-            frost.clone(),
-            xonly_frost_key.clone(),
-            0,
-            secret_shares[0].clone(),
-            message,
-        );
-        let (mut signer2, nonce2) = signer::RoastSigner::new(
-            &mut rng,
-            frost,
-            xonly_frost_key.clone(),
-            1,
-            secret_shares[1].clone(),
-            message,
-        );
+        let (_, roast, mut signers, nonces) = setup(2, 3, message, &mut rng);
 
         // Begin with each signer sending a nonce to ROAST, marking these signers as responsive.
-        let response = roast.receive(0, None, nonce1).unwrap();
-        assert!(response.nonce_set.is_none());
-        assert!(response.combined_signature.is_none());
+        let response = roast.receive(0, nonces[0].clone()).unwrap();
+        assert!(response.message.is_none());
 
-        let response2 = roast.receive(1, None, nonce2).unwrap();
-        assert!(response2.nonce_set.is_some());
+        let response2 = roast.receive(1, nonces[1].clone()).unwrap();
 
         // Once ROAST receives the threshold number of nonces, it responds to the group of
         // responsive signers with a nonce set to the group of responsive signers.
         assert!(response2.recipients.contains(&0) && response2.recipients.contains(&1));
-        let sign_session_nonces = response2.nonce_set.expect("roast responded with nonces");
+        let sign_request = response2.message.expect("roast responded with nonces");
+        assert!(matches!(
+            sign_request,
+            CoordinatorMessage::SignRequest { .. }
+        ));
 
         // The signer signs using this the nonces for this sign session,
         // and responds to ROAST with a signature share.
-        let (sig_share2, nonce2) = signer2.sign(&mut rng, sign_session_nonces.clone());
-        let response = roast.receive(1, Some(sig_share2), nonce2).unwrap();
-        dbg!(
-            &response.combined_signature.is_some(),
-            &response.nonce_set.is_some()
-        );
-        assert!(response.combined_signature.is_none());
+        let reply2 = signers[1]
+            .receive(&mut rng, sign_request.clone())
+            .unwrap()
+            .expect("signer replies to sign request");
+        let response = roast.receive(1, reply2).unwrap();
+        assert!(response.message.is_none());
 
         // ROAST also sends the nonce set to the other signer, who also signs
-        let (sig_share1, nonce1) = signer1.sign(&mut rng, sign_session_nonces);
-
-        let response = roast.receive(0, Some(sig_share1), nonce1).unwrap();
-        dbg!(
-            &response.combined_signature.is_some(),
-            &response.nonce_set.is_some()
-        );
-        assert!(response.combined_signature.is_some());
+        let reply1 = signers[0]
+            .receive(&mut rng, sign_request)
+            .unwrap()
+            .expect("signer replies to sign request");
+        let response = roast.receive(0, reply1).unwrap();
 
         // Once the threshold number of signature shares have been received,
         // ROAST combines the signature shares into the aggregate signature
-        dbg!(response.combined_signature);
+        assert_eq!(response.recipients, vec![0, 1, 2]);
+        let completed = response.message.expect("roast responded with signature");
+        assert!(matches!(completed, CoordinatorMessage::Completed { .. }));
+        assert!(signers[0].receive(&mut rng, completed).unwrap().is_none());
+        assert!(signers[0].signature().is_some());
+    }
+
+    #[test]
+    fn malicious_signers_stay_banned_across_messages() {
+        let mut rng = rand::thread_rng();
+
+        // Signer 0 disrupts the first message with a bogus signature share
+        let message = Message::plain("test", b"first");
+        let (group, roast, mut signers, nonces) = setup(2, 3, message, &mut rng);
+        roast.receive(0, nonces[0].clone()).unwrap();
+        let response = roast.receive(1, nonces[1].clone()).unwrap();
+        let sign_request = response.message.expect("roast responded with nonces");
+        let reply1 = signers[1].receive(&mut rng, sign_request).unwrap().unwrap();
+        roast.receive(1, reply1).unwrap();
+        let bogus_reply = SignerMessage::ShareAndNonce {
+            message_id: nonces[0].message_id(),
+            session_id: 1,
+            signature_share: Scalar::random(&mut rng).mark_zero().public(),
+            nonce: nonces[0].nonce(),
+        };
+        let response = roast.receive(0, bogus_reply).unwrap();
+        assert_eq!(response.message, Some(CoordinatorMessage::Banned));
+        assert!(roast.malicious_signers().contains(&0));
+
+        // The next message is signed by the remaining signers, and signer 0 is ignored
+        let message = Message::plain("test", b"second");
+        roast.start_session(message).unwrap();
+        let (_, nonces) = group.signers([0], message, &mut rng);
+        let response = roast.receive(0, nonces[0].clone()).unwrap();
+        let (mut signers, nonces) = group.signers(1..3, message, &mut rng);
+
+        assert_eq!(response.recipients, vec![0]);
+        assert_eq!(response.message, Some(CoordinatorMessage::Banned));
+        roast.receive(1, nonces[0].clone()).unwrap();
+        let response = roast.receive(2, nonces[1].clone()).unwrap();
+        let sign_request = response.message.expect("roast responded with nonces");
+        match &sign_request {
+            CoordinatorMessage::SignRequest { nonce_set, .. } => {
+                assert!(nonce_set.iter().all(|(i, _)| *i != 0))
+            }
+            _ => panic!("expected a sign request"),
+        }
+
+        let mut combined_signature = None;
+        for (signer, i) in signers.iter_mut().zip(1..3) {
+            let reply = signer
+                .receive(&mut rng, sign_request.clone())
+                .unwrap()
+                .unwrap();
+            if let Some(CoordinatorMessage::Completed { signature }) =
+                roast.receive(i, reply).unwrap().message
+            {
+                combined_signature = Some(signature);
+            }
+        }
+        assert!(group.frost.schnorr.verify(
+            &group.joint_key.public_key(),
+            message,
+            &combined_signature.expect("signature was combined")
+        ));
+
+        // Signer 1 has taken part in both messages
+        let record = roast.signer_record(1);
+        assert_eq!(record.sessions_joined, 2);
+        assert_eq!(record.shares_accepted, 2);
+    }
+
+    #[test]
+    fn malformed_messages_are_rejected() {
+        let mut rng = rand::thread_rng();
+        let message = Message::plain("test", b"test");
+        let group = Group::new(2, 3, &mut rng);
+        let roast = group.coordinator();
+        let (mut signers, nonces) = group.signers(0..3, message, &mut rng);
+        let bogus_reply = SignerMessage::ShareAndNonce {
+            message_id: nonces[0].message_id(),
+            session_id: 1,
+            signature_share: Scalar::random(&mut rng).mark_zero().public(),
+            nonce: nonces[0].nonce(),
+        };
+
+        assert_eq!(
+            roast.receive(0, nonces[0].clone()).unwrap_err(),
+            RoastError::NoActiveSession
+        );
+        roast.start_session(message).unwrap();
+
+        assert_eq!(
+            roast.receive(3, nonces[0].clone()).unwrap_err(),
+            RoastError::UnknownSigner(3)
+        );
+        assert_eq!(
+            roast.receive(0, bogus_reply).unwrap_err(),
+            RoastError::UnexpectedSignatureShare(0)
+        );
+
+        // Signers 0 and 1 are placed in the first sign session
+        roast.receive(0, nonces[0].clone()).unwrap();
+        let session_1 = roast
+            .receive(1, nonces[1].clone())
+            .unwrap()
+            .message
+            .expect("roast responded with nonces");
+        let another_nonce = SignerMessage::NonceOnly {
+            message_id: nonces[0].message_id(),
+            nonce: NonceKeyPair::random(&mut rng).public(),
+        };
+        assert_eq!(
+            roast.receive(0, another_nonce).unwrap_err(),
+            RoastError::MissingSignatureShare(0)
+        );
+
+        // Signers 1 and 2 are placed in the second sign session
+        roast.receive(2, nonces[2].clone()).unwrap();
+        let reply = signers[1]
+            .receive(&mut rng, session_1.clone())
+            .unwrap()
+            .unwrap();
+        let session_2 = roast
+            .receive(1, reply)
+            .unwrap()
+            .message
+            .expect("roast responded with nonces");
+
+        // The first session completes, so the late share of the second session is answered
+        // with the signature
+        let reply = signers[0].receive(&mut rng, session_1).unwrap().unwrap();
+        let response = roast.receive(0, reply).unwrap();
+        assert!(matches!(
+            response.message,
+            Some(CoordinatorMessage::Completed { .. })
+        ));
+        let reply = signers[2].receive(&mut rng, session_2).unwrap().unwrap();
+        let late = roast.receive(2, reply).unwrap();
+        assert_eq!(late.recipients, vec![2]);
+        assert_eq!(late.message, response.message);
+
+        // None of the rejected messages marked anyone as malicious
+        assert!(roast.malicious_signers().is_empty());
+    }
+
+    #[test]
+    fn copied_nonces_are_banned() {
+        let mut rng = rand::thread_rng();
+        let message = Message::plain("test", b"copied nonces");
+        let events = Arc::new(Mutex::new(vec![]));
+        let observed = events.clone();
+        let group = Group::new(2, 4, &mut rng);
+        let (_, nonces) = group.signers(0..4, message, &mut rng);
+        let roast = group
+            .coordinator()
+            .with_observer(move |event: &CoordinatorEvent| {
+                observed.lock().unwrap().push(event.clone())
+            });
+        roast.start_session(message).unwrap();
+        let copy = |index: usize| SignerMessage::NonceOnly {
+            message_id: nonces[index].message_id(),
+            nonce: nonces[index].nonce(),
+        };
+
+        // A nonce still waiting for a sign session is as good as used
+        roast.receive(0, nonces[0].clone()).unwrap();
+        assert_eq!(
+            roast.receive(1, copy(0)).unwrap().message,
+            Some(CoordinatorMessage::Banned)
+        );
+
+        // As is one handed out in a sign session
+        let request = roast.receive(2, nonces[2].clone()).unwrap().message;
+        assert!(matches!(
+            request,
+            Some(CoordinatorMessage::SignRequest { .. })
+        ));
+        assert_eq!(
+            roast.receive(3, copy(2)).unwrap().message,
+            Some(CoordinatorMessage::Banned)
+        );
+
+        let bans: Vec<_> = events
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(|event| match event {
+                CoordinatorEvent::SignerBanned { index, reason } => Some((*index, *reason)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            bans,
+            vec![(1, BanReason::ReusedNonce), (3, BanReason::ReusedNonce)]
+        );
+    }
+
+    #[test]
+    fn delayed_shares_are_not_misattributed() {
+        let mut rng = rand::thread_rng();
+        let message = Message::plain("test", b"test");
+        let (_, roast, mut signers, nonces) = setup(2, 3, message, &mut rng);
+
+        // Signer 1 answers session 1 and is immediately placed in session 2
+        roast.receive(0, nonces[0].clone()).unwrap();
+        let session_1 = roast
+            .receive(1, nonces[1].clone())
+            .unwrap()
+            .message
+            .unwrap();
+        roast.receive(2, nonces[2].clone()).unwrap();
+        let reply = signers[1].receive(&mut rng, session_1).unwrap().unwrap();
+        let response = roast.receive(1, reply.clone()).unwrap();
+        match response.message {
+            Some(CoordinatorMessage::SignRequest { session_id, .. }) => assert_eq!(session_id, 2),
+            _ => panic!("expected a sign request"),
+        }
+
+        // A delayed share for session 1 is not verified against session 2
+        let delayed = SignerMessage::ShareAndNonce {
+            message_id: reply.message_id(),
+            session_id: 1,
+            signature_share: reply.signature_share().unwrap(),
+            nonce: NonceKeyPair::random(&mut rng).public(),
+        };
+        assert_eq!(
+            roast.receive(1, delayed).unwrap_err(),
+            RoastError::StaleSession {
+                index: 1,
+                session_id: 1
+            }
+        );
+        let unknown = SignerMessage::ShareAndNonce {
+            message_id: reply.message_id(),
+            session_id: 7,
+            signature_share: reply.signature_share().unwrap(),
+            nonce: reply.nonce(),
+        };
+        assert_eq!(
+            roast.receive(1, unknown).unwrap_err(),
+            RoastError::UnknownSession {
+                index: 1,
+                session_id: 7
+            }
+        );
+
+        // Signer 2 was never part of session 1, so the session is unknown to it
+        let outsider = SignerMessage::ShareAndNonce {
+            message_id: reply.message_id(),
+            session_id: 1,
+            signature_share: reply.signature_share().unwrap(),
+            nonce: NonceKeyPair::random(&mut rng).public(),
+        };
+        assert_eq!(
+            roast.receive(2, outsider).unwrap_err(),
+            RoastError::UnknownSession {
+                index: 2,
+                session_id: 1
+            }
+        );
+        assert!(roast.malicious_signers().is_empty());
+    }
+
+    #[test]
+    fn retransmissions_are_answered_idempotently() {
+        let mut rng = rand::thread_rng();
+        let message = Message::plain("test", b"test");
+        let (group, roast, mut signers, nonces) = setup(2, 3, message, &mut rng);
+
+        // A redelivered nonce is not an unsolicited reply
+        let response = roast.receive(0, nonces[0].clone()).unwrap();
+        assert_eq!(roast.receive(0, nonces[0].clone()).unwrap(), response);
+
+        // A redelivered nonce which opened a session is answered with the same sign request
+        let response = roast.receive(1, nonces[1].clone()).unwrap();
+        assert_eq!(response.recipients.len(), 2);
+        let replayed = roast.receive(1, nonces[1].clone()).unwrap();
+        assert_eq!(replayed.recipients, vec![1]);
+        assert_eq!(replayed.message, response.message);
+
+        // A redelivered share is answered the same way
+        let sign_request = response.message.unwrap();
+        let reply = signers[0].receive(&mut rng, sign_request).unwrap().unwrap();
+        let response = roast.receive(0, reply.clone()).unwrap();
+        assert_eq!(roast.receive(0, reply).unwrap(), response);
+        assert!(roast.malicious_signers().is_empty());
+
+        // A late duplicate of an earlier message is ignored, also by a coordinator restored from
+        // the stored state
+        assert_eq!(roast.receive(0, nonces[0].clone()).unwrap().message, None);
+        let mut store = MemoryStore::new();
+        store.save(&roast.state()).unwrap();
+        let roast = group.coordinator().with_store(store).unwrap();
+        assert_eq!(roast.receive(0, nonces[0].clone()).unwrap().message, None);
+        assert!(roast.malicious_signers().is_empty());
+
+        // A conflicting message is still unsolicited
+        let conflicting = SignerMessage::NonceOnly {
+            message_id: nonces[0].message_id(),
+            nonce: NonceKeyPair::random(&mut rng).public(),
+        };
+        let response = roast.receive(0, conflicting).unwrap();
+        assert_eq!(response.message, Some(CoordinatorMessage::Banned));
+        assert!(roast.malicious_signers().contains(&0));
+    }
+
+    #[test]
+    fn signer_signs_with_the_nonce_it_shared() {
+        let mut rng = rand::thread_rng();
+        let message = Message::plain("test", b"test");
+        let (_, roast, mut signers, nonces) = setup(2, 2, message, &mut rng);
+
+        // Signer 0 generates another nonce which it never shares
+        signers[0].new_nonce(&mut rng);
+
+        roast.receive(0, nonces[0].clone()).unwrap();
+        let sign_request = roast
+            .receive(1, nonces[1].clone())
+            .unwrap()
+            .message
+            .unwrap();
+        let reply = signers[0]
+            .receive(&mut rng, sign_request.clone())
+            .unwrap()
+            .unwrap();
+        assert!(roast.receive(0, reply).unwrap().message.is_none());
+
+        // The nonce has been erased, so the signer refuses to sign with it again
+        assert_eq!(
+            signers[0].receive(&mut rng, sign_request.clone()),
+            Err(SignerError::NoMatchingNonce)
+        );
+
+        let reply = signers[1].receive(&mut rng, sign_request).unwrap().unwrap();
+        let response = roast.receive(1, reply).unwrap();
+        assert!(matches!(
+            response.message,
+            Some(CoordinatorMessage::Completed { .. })
+        ));
+        assert!(roast.malicious_signers().is_empty());
+    }
+
+    #[test]
+    fn signer_rejects_malformed_nonce_sets() {
+        let mut rng = rand::thread_rng();
+        let message = Message::plain("test", b"test");
+        let (mut signers, nonces) = Group::new(2, 3, &mut rng).signers([0], message, &mut rng);
+        let signer = &mut signers[0];
+        let my_nonce = nonces[0].nonce();
+        let other_nonce = NonceKeyPair::random(&mut rng).public();
+        let sign_request = |session_id, nonce_set| CoordinatorMessage::SignRequest {
+            message_id: nonces[0].message_id(),
+            session_id,
+            nonce_set,
+            announcement_signature: None,
+        };
+
+        for (nonce_set, error) in [
+            (
+                vec![(0, my_nonce), (3, other_nonce)],
+                SignerError::UnknownSigner(3),
+            ),
+            (
+                vec![(0, my_nonce), (1, other_nonce), (1, other_nonce)],
+                SignerError::DuplicateIndex(1),
+            ),
+            (vec![(0, my_nonce)], SignerError::TooFewNonces(1)),
+            (
+                vec![(1, other_nonce), (2, my_nonce)],
+                SignerError::NotInNonceSet,
+            ),
+            (
+                vec![(0, other_nonce), (1, my_nonce)],
+                SignerError::NoMatchingNonce,
+            ),
+        ] {
+            assert_eq!(
+                signer
+                    .receive(&mut rng, sign_request(1, nonce_set.clone()))
+                    .unwrap_err(),
+                error
+            );
+            assert_eq!(signer.validate_nonce_set(&nonce_set).unwrap_err(), error);
+        }
+
+        // Once signed, no nonce from the set can appear in a later set
+        let reply = signer
+            .receive(
+                &mut rng,
+                sign_request(1, vec![(0, my_nonce), (1, other_nonce)]),
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            signer
+                .receive(
+                    &mut rng,
+                    sign_request(2, vec![(0, reply.nonce()), (1, other_nonce)])
+                )
+                .unwrap_err(),
+            SignerError::ReusedNonce(1)
+        );
+    }
+
+    #[test]
+    fn observer_sees_every_state_transition() {
+        let mut rng = rand::thread_rng();
+        let message = Message::plain("test", b"test");
+        let group = Group::new(2, 3, &mut rng);
+
+        let events = Arc::new(Mutex::new(vec![]));
+        let observed = events.clone();
+        let roast = group
+            .coordinator()
+            .with_observer(move |event: &CoordinatorEvent| {
+                observed.lock().unwrap().push(event.clone())
+            });
+        roast.start_session(message).unwrap();
+        let (mut signers, nonces) = group.signers(0..3, message, &mut rng);
+
+        roast.receive(0, nonces[0].clone()).unwrap();
+        roast.receive(0, nonces[0].clone()).unwrap();
+        let sign_request = roast
+            .receive(1, nonces[1].clone())
+            .unwrap()
+            .message
+            .unwrap();
+        let bogus_reply = SignerMessage::ShareAndNonce {
+            message_id: nonces[0].message_id(),
+            session_id: 1,
+            signature_share: Scalar::random(&mut rng).mark_zero().public(),
+            nonce: NonceKeyPair::random(&mut rng).public(),
+        };
+        roast.receive(0, bogus_reply).unwrap();
+        roast.receive(0, nonces[0].clone()).unwrap();
+        let reply = signers[1].receive(&mut rng, sign_request).unwrap().unwrap();
+        roast.receive(1, reply).unwrap();
+        let sign_request = roast
+            .receive(2, nonces[2].clone())
+            .unwrap()
+            .message
+            .unwrap();
+        for (i, signer) in signers.iter_mut().enumerate().skip(1) {
+            let reply = signer
+                .receive(&mut rng, sign_request.clone())
+                .unwrap()
+                .unwrap();
+            roast.receive(i, reply).unwrap();
+        }
+
+        let mut events = events.lock().unwrap().clone();
+        for event in &mut events {
+            if let CoordinatorEvent::SignSessionOpened { signers, .. } = event {
+                signers.sort();
+            }
+        }
+        let signature = match events.last() {
+            Some(CoordinatorEvent::SignatureCombined { signature, .. }) => signature.clone(),
+            _ => panic!("expected the signature to be combined"),
+        };
+        assert_eq!(
+            events,
+            vec![
+                CoordinatorEvent::SessionStarted,
+                CoordinatorEvent::SignerResponsive { index: 0 },
+                CoordinatorEvent::RetransmissionReplayed { index: 0 },
+                CoordinatorEvent::SignerResponsive { index: 1 },
+                CoordinatorEvent::SignSessionOpened {
+                    session_id: 1,
+                    signers: vec![0, 1]
+                },
+                CoordinatorEvent::ShareRejected {
+                    index: 0,
+                    session_id: 1
+                },
+                CoordinatorEvent::SignerBanned {
+                    index: 0,
+                    reason: BanReason::InvalidSignatureShare
+                },
+                CoordinatorEvent::BannedSignerIgnored { index: 0 },
+                CoordinatorEvent::ShareAccepted {
+                    index: 1,
+                    session_id: 1
+                },
+                CoordinatorEvent::SignerResponsive { index: 1 },
+                CoordinatorEvent::SignerResponsive { index: 2 },
+                CoordinatorEvent::SignSessionOpened {
+                    session_id: 2,
+                    signers: vec![1, 2]
+                },
+                CoordinatorEvent::ShareAccepted {
+                    index: 1,
+                    session_id: 2
+                },
+                CoordinatorEvent::SignerResponsive { index: 1 },
+                CoordinatorEvent::ShareAccepted {
+                    index: 2,
+                    session_id: 2
+                },
+                CoordinatorEvent::SignatureCombined {
+                    session_id: 2,
+                    signature
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn protocol_messages_roundtrip_through_json() {
+        let mut rng = rand::thread_rng();
+        let nonce = NonceKeyPair::random(&mut rng).public();
+        let signer_message = SignerMessage::ShareAndNonce {
+            message_id: [7; 32],
+            session_id: 3,
+            signature_share: Scalar::random(&mut rng).mark_zero().public(),
+            nonce,
+        };
+        let json = serde_json::to_string(&signer_message).unwrap();
+        assert_eq!(
+            serde_json::from_str::<SignerMessage>(&json).unwrap(),
+            signer_message
+        );
+
+        let coordinator_message = CoordinatorMessage::SignRequest {
+            message_id: [7; 32],
+            session_id: 3,
+            nonce_set: vec![(0, nonce), (2, nonce)],
+            announcement_signature: None,
+        };
+        let json = serde_json::to_string(&coordinator_message).unwrap();
+        assert_eq!(
+            serde_json::from_str::<CoordinatorMessage>(&json).unwrap(),
+            coordinator_message
+        );
+    }
+
+    #[test]
+    fn malformed_nonces_are_rejected_on_the_wire() {
+        use roast::wire::{read_frame, write_frame, SignerFrame};
+
+        let nonce = NonceKeyPair::random(&mut rand::thread_rng()).public();
+        let frame = SignerFrame::Message(SignerMessage::NonceOnly {
+            message_id: [7; 32],
+            nonce,
+        });
+        let encoded = serde_json::to_value(&frame).unwrap()["Message"]["NonceOnly"]["nonce"]
+            .as_str()
+            .unwrap()
+            .to_owned();
+
+        let mut bytes = vec![];
+        write_frame(&mut bytes, &frame).unwrap();
+        assert_eq!(read_frame::<SignerFrame>(&mut &bytes[..]).unwrap(), frame);
+
+        for malformed in [
+            // The identity, which has no compressed encoding
+            "00".repeat(66),
+            // A point with an unknown prefix
+            format!("05{}", &encoded[2..]),
+            // A single point
+            encoded[..66].to_owned(),
+        ] {
+            let mut json = serde_json::to_value(&frame).unwrap();
+            json["Message"]["NonceOnly"]["nonce"] = malformed.into();
+            let mut bytes = vec![];
+            write_frame(&mut bytes, &json).unwrap();
+            assert_eq!(
+                read_frame::<SignerFrame>(&mut &bytes[..])
+                    .unwrap_err()
+                    .kind(),
+                std::io::ErrorKind::InvalidData
+            );
+        }
+    }
+
+    #[test]
+    fn coordinator_resumes_from_stored_state() {
+        let mut rng = rand::thread_rng();
+        let message = Message::plain("test", b"restart");
+        let group = Group::new(2, 4, &mut rng);
+        let store = MemoryStore::new();
+        let roast = group.coordinator().with_store(store.clone()).unwrap();
+        roast.start_session(message).unwrap();
+        let (mut signers, nonces) = group.signers(0..3, message, &mut rng);
+
+        // A session is opened for signers 0 and 1, then signer 2 is banned for an unsolicited message
+        roast.receive(0, nonces[0].clone()).unwrap();
+        let sign_request = roast
+            .receive(1, nonces[1].clone())
+            .unwrap()
+            .message
+            .expect("session opened");
+        roast.receive(2, nonces[2].clone()).unwrap();
+        let (_, another_nonce) = group.signers([2], message, &mut rng);
+        roast.receive(2, another_nonce[0].clone()).unwrap();
+        let reply0 = signers[0]
+            .receive(&mut rng, sign_request.clone())
+            .unwrap()
+            .unwrap();
+        roast.receive(0, reply0.clone()).unwrap();
+        let state = roast.state();
+
+        // The coordinator crashes and a new one is restored from the store
+        drop(roast);
+
+        // The state is not restored into a coordinator for another group
+        let other_group = Group::new(2, 4, &mut rng);
+        let error = other_group
+            .coordinator()
+            .with_store(store.clone())
+            .err()
+            .unwrap();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+
+        let roast = group.coordinator().with_store(store).unwrap();
+        assert_eq!(roast.state(), state);
+        assert!(roast.malicious_signers().contains(&2));
+
+        // Retransmissions are still recognised and the open session completes
+        assert_eq!(roast.receive(0, reply0).unwrap().recipients, vec![0]);
+        let reply1 = signers[1].receive(&mut rng, sign_request).unwrap().unwrap();
+        let completed = roast.receive(1, reply1).unwrap().message;
+        let signature = match completed {
+            Some(CoordinatorMessage::Completed { signature }) => signature,
+            other => panic!("expected a signature, got {:?}", other),
+        };
+        assert!(group
+            .frost
+            .schnorr
+            .verify(&group.joint_key.public_key(), message, &signature));
+    }
+
+    #[test]
+    fn file_store_recovers_the_last_complete_entry() {
+        let mut rng = rand::thread_rng();
+        let message = Message::plain("test", b"file");
+        let group = Group::new(2, 3, &mut rng);
+        let path = std::env::temp_dir().join(format!("roast-wal-{}.log", rand::random::<u64>()));
+
+        // Room for two entries of the initial state, which is smaller than every later one
+        let max_size = 2 * serde_json::to_vec(&group.coordinator().state())
+            .unwrap()
+            .len() as u64;
+        let roast = group
+            .coordinator()
+            .with_store(FileStore::open(&path).unwrap().with_max_size(max_size))
+            .unwrap();
+        roast.start_session(message).unwrap();
+        let (_, nonces) = group.signers(0..2, message, &mut rng);
+        for (i, nonce) in nonces.into_iter().enumerate() {
+            roast.receive(i, nonce).unwrap();
+        }
+        let state = roast.state();
+        drop(roast);
+
+        // The log was compacted rather than growing with every message
+        let log = std::fs::read_to_string(&path).unwrap();
+        assert!(log.lines().count() < 4);
+        assert!(log.len() as u64 <= max_size || log.lines().count() == 1);
+
+        // A crash midway through writing an entry leaves a torn line at the end of the log
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap();
+        std::io::Write::write_all(&mut file, b"{\"malicious_signers\":[").unwrap();
+        drop(file);
+
+        let mut store = FileStore::open(&path).unwrap();
+        assert_eq!(store.load().unwrap(), Some(state.clone()));
+
+        // New entries are not corrupted by the torn line
+        store.save(&state).unwrap();
+        let mut store = FileStore::open(&path).unwrap();
+        assert_eq!(store.load().unwrap(), Some(state));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn blame_proofs_confirm_invalid_shares() {
+        let mut rng = rand::thread_rng();
+        let message = Message::plain("test", b"blame");
+        let (group, roast, mut signers, nonces) = setup(2, 3, message, &mut rng);
+        let frost = &group.frost;
+        roast.receive(0, nonces[0].clone()).unwrap();
+        let sign_request = roast
+            .receive(1, nonces[1].clone())
+            .unwrap()
+            .message
+            .unwrap();
+
+        // Signer 1 is honest while signer 0 replaces its share
+        let honest_reply = signers[1]
+            .receive(&mut rng, sign_request.clone())
+            .unwrap()
+            .unwrap();
+        roast.receive(1, honest_reply.clone()).unwrap();
+        let bogus_reply = match signers[0].receive(&mut rng, sign_request).unwrap().unwrap() {
+            SignerMessage::ShareAndNonce {
+                message_id,
+                session_id,
+                nonce,
+                ..
+            } => SignerMessage::ShareAndNonce {
+                message_id,
+                session_id,
+                signature_share: Scalar::random(&mut rng).public().mark_zero(),
+                nonce,
+            },
+            other => panic!("expected a share, got {:?}", other),
+        };
+        roast.receive(0, bogus_reply).unwrap();
+        assert!(roast.blame_proof(1).is_none());
+
+        // The proof survives serialization and can be checked by anyone
+        let proof = roast.blame_proof(0).expect("signer 0 was blamed");
+        let proof: BlameProof<_> =
+            serde_json::from_str(&serde_json::to_string(&proof).unwrap()).unwrap();
+        assert_eq!(proof.index, 0);
+        assert!(verify_blame_proof(frost, &proof));
+
+        // Blaming signer 1 with its valid share does not hold up
+        let framed = BlameProof {
+            index: 1,
+            signature_share: honest_reply.signature_share().unwrap(),
+            ..proof.clone()
+        };
+        assert!(!verify_blame_proof(frost, &framed));
+
+        // Nor does blaming a signer outside the nonce set
+        let outsider = BlameProof { index: 2, ..proof };
+        assert!(!verify_blame_proof(frost, &outsider));
+    }
+
+    #[test]
+    fn optimistic_verification_blames_only_when_the_signature_fails() {
+        let mut rng = rand::thread_rng();
+        let message = Message::plain("test", b"optimistic");
+        let group = Group::new(3, 4, &mut rng);
+        let events = Arc::new(Mutex::new(vec![]));
+        let observed = events.clone();
+        let roast = group
+            .coordinator()
+            .with_optimistic_verification()
+            .with_observer(move |event: &CoordinatorEvent| {
+                observed.lock().unwrap().push(event.clone())
+            });
+        roast.start_session(message).unwrap();
+        let (mut signers, nonces) = group.signers(0..4, message, &mut rng);
+
+        roast.receive(0, nonces[0].clone()).unwrap();
+        roast.receive(1, nonces[1].clone()).unwrap();
+        let first_request = roast
+            .receive(2, nonces[2].clone())
+            .unwrap()
+            .message
+            .unwrap();
+        roast.receive(3, nonces[3].clone()).unwrap();
+
+        // Honest shares are stored without being checked, until their signers are asked to sign
+        // again in a second session
+        let reply = signers[1]
+            .receive(&mut rng, first_request.clone())
+            .unwrap()
+            .unwrap();
+        roast.receive(1, reply).unwrap();
+        let reply = signers[2]
+            .receive(&mut rng, first_request.clone())
+            .unwrap()
+            .unwrap();
+        let second_request = roast.receive(2, reply).unwrap();
+        assert_eq!(second_request.recipients.len(), 3);
+        let share_checks = |events: &[CoordinatorEvent]| {
+            events
+                .iter()
+                .filter(|event| {
+                    matches!(
+                        event,
+                        CoordinatorEvent::ShareAccepted { .. }
+                            | CoordinatorEvent::ShareRejected { .. }
+                    )
+                })
+                .count()
+        };
+        assert_eq!(share_checks(&events.lock().unwrap()), 2);
+
+        // The bad share spoils the combined signature, so every share is checked to find it
+        let bogus_reply = match signers[0]
+            .receive(&mut rng, first_request)
+            .unwrap()
+            .unwrap()
+        {
+            SignerMessage::ShareAndNonce {
+                message_id,
+                session_id,
+                nonce,
+                ..
+            } => SignerMessage::ShareAndNonce {
+                message_id,
+                session_id,
+                signature_share: Scalar::random(&mut rng).public().mark_zero(),
+                nonce,
+            },
+            other => panic!("expected a share, got {:?}", other),
+        };
+        assert_eq!(
+            roast.receive(0, bogus_reply).unwrap().message,
+            Some(CoordinatorMessage::Banned)
+        );
+        assert_eq!(share_checks(&events.lock().unwrap()), 3);
+        assert_eq!(roast.malicious_signers(), [0].into_iter().collect());
+        assert!(verify_blame_proof(
+            &group.frost,
+            &roast.blame_proof(0).unwrap()
+        ));
+        assert_eq!(roast.signer_record(1).shares_accepted, 1);
+
+        // The honest second session completes with a single check of the combined signature
+        let mut completed = None;
+        for i in second_request.recipients {
+            let reply = signers[i]
+                .receive(&mut rng, second_request.message.clone().unwrap())
+                .unwrap()
+                .unwrap();
+            completed = roast.receive(i, reply).unwrap().message;
+        }
+        let signature = match completed {
+            Some(CoordinatorMessage::Completed { signature }) => signature,
+            other => panic!("expected a signature, got {:?}", other),
+        };
+        assert!(group
+            .frost
+            .schnorr
+            .verify(&group.joint_key.public_key(), message, &signature));
+        assert_eq!(share_checks(&events.lock().unwrap()), 6);
+    }
+
+    #[test]
+    fn optimistic_verification_keeps_the_session_bound() {
+        let mut rng = rand::thread_rng();
+        let message = Message::plain("test", b"optimistic bound");
+        let group = Group::new(2, 4, &mut rng);
+        let roast = group.coordinator().with_optimistic_verification();
+        roast.start_session(message).unwrap();
+        let (mut signers, nonces) = group.signers(0..4, message, &mut rng);
+        let bogus = |reply| match reply {
+            SignerMessage::ShareAndNonce {
+                message_id,
+                session_id,
+                nonce,
+                ..
+            } => SignerMessage::ShareAndNonce {
+                message_id,
+                session_id,
+                signature_share: Scalar::random(&mut rand::thread_rng()).public().mark_zero(),
+                nonce,
+            },
+            other => panic!("expected a share, got {:?}", other),
+        };
+
+        // Signer 3 answers its first session with an invalid share, which is checked before it
+        // could be asked to sign again alongside signer 1
+        roast.receive(3, nonces[3].clone()).unwrap();
+        let first_request = roast
+            .receive(0, nonces[0].clone())
+            .unwrap()
+            .message
+            .unwrap();
+        let reply = signers[3]
+            .receive(&mut rng, first_request)
+            .unwrap()
+            .unwrap();
+        assert_eq!(roast.receive(3, bogus(reply)).unwrap().message, None);
+        assert_eq!(roast.receive(1, nonces[1].clone()).unwrap().message, None);
+        assert_eq!(roast.malicious_signers(), [3].into_iter().collect());
+        assert!(verify_blame_proof(
+            &group.frost,
+            &roast.blame_proof(3).unwrap()
+        ));
+
+        // The honest signers are asked to sign in only the second session, within the n - t + 1
+        // session bound, and complete it
+        let second_request = roast.receive(2, nonces[2].clone()).unwrap();
+        assert!(matches!(
+            second_request.message,
+            Some(CoordinatorMessage::SignRequest { session_id: 2, .. })
+        ));
+        let mut completed = None;
+        for i in second_request.recipients {
+            let reply = signers[i]
+                .receive(&mut rng, second_request.message.clone().unwrap())
+                .unwrap()
+                .unwrap();
+            completed = roast.receive(i, reply).unwrap().message;
+        }
+        assert!(matches!(
+            completed,
+            Some(CoordinatorMessage::Completed { .. })
+        ));
+    }
+
+    #[test]
+    fn completed_coordinator_answers_with_the_signature() {
+        let mut rng = rand::thread_rng();
+        let message = Message::plain("test", b"completed");
+        let group = Group::new(2, 4, &mut rng);
+        let store = MemoryStore::new();
+        let roast = group.coordinator().with_store(store.clone()).unwrap();
+        roast.start_session(message).unwrap();
+        let (mut signers, nonces) = group.signers(0..4, message, &mut rng);
+
+        // Two sign sessions are opened, and the first completes
+        roast.receive(0, nonces[0].clone()).unwrap();
+        let first_request = roast.receive(1, nonces[1].clone()).unwrap();
+        roast.receive(2, nonces[2].clone()).unwrap();
+        let second_request = roast
+            .receive(3, nonces[3].clone())
+            .unwrap()
+            .message
+            .unwrap();
+        assert!(roast.result().is_none());
+        let mut completed = None;
+        for i in first_request.recipients {
+            let reply = signers[i]
+                .receive(&mut rng, first_request.message.clone().unwrap())
+                .unwrap()
+                .unwrap();
+            completed = Some(roast.receive(i, reply).unwrap());
+        }
+        let completed = completed.unwrap();
+        assert_eq!(completed.recipients.len(), 4);
+        let signature = match completed.message.clone().unwrap() {
+            CoordinatorMessage::Completed { signature } => signature,
+            other => panic!("expected a signature, got {:?}", other),
+        };
+        assert_eq!(roast.result(), Some(signature.clone()));
+
+        // Signers of the other session discard their nonces and can no longer sign for it
+        assert!(signers[2]
+            .receive(&mut rng, completed.message.clone().unwrap())
+            .unwrap()
+            .is_none());
+        assert_eq!(signers[2].signature(), Some(signature.clone()));
+        assert_eq!(
+            signers[2].receive(&mut rng, second_request.clone()),
+            Err(SignerError::NoMatchingNonce)
+        );
+
+        // Any further message is answered with the signature, and no session is opened
+        let late_reply = signers[3]
+            .receive(&mut rng, second_request)
+            .unwrap()
+            .unwrap();
+        for (i, late) in [(3, late_reply), (0, nonces[0].clone())] {
+            let response = roast.receive(i, late).unwrap();
+            assert_eq!(response.recipients, vec![i]);
+            assert_eq!(response.message, completed.message);
+        }
+        assert!(roast.open_session().unwrap().is_none());
+
+        // The completion survives a restart
+        let restored = group.coordinator().with_store(store).unwrap();
+        assert_eq!(restored.result(), Some(signature));
+    }
+
+    #[test]
+    fn invalid_combined_signatures_are_never_broadcast() {
+        use roast::coordinator::StoredMessage;
+        use roast::threshold_scheme::ThresholdScheme;
+        use schnorr_fun::frost::SignSession;
+        use schnorr_fun::musig::Nonce;
+        use schnorr_fun::Signature;
+        use secp256kfun::marker::{Public, Zero};
+
+        /// FROST, except that combining shares yields a corrupted signature
+        #[derive(Clone)]
+        struct BrokenCombine(TestFrost);
+
+        impl ThresholdScheme<FrostKey<EvenY>> for BrokenCombine {
+            type PublicNonce = Nonce;
+            type SecretNonce = NonceKeyPair;
+            type SecretShare = Scalar;
+            type SignatureShare = Scalar<Public, Zero>;
+            type Signature = Signature;
+            type Message = StoredMessage;
+            type SignSession = SignSession;
+
+            fn threshold(&self, joint_key: &FrostKey<EvenY>) -> usize {
+                ThresholdScheme::threshold(&self.0, joint_key)
+            }
+
+            fn n_signers(&self, joint_key: &FrostKey<EvenY>) -> usize {
+                ThresholdScheme::n_signers(&self.0, joint_key)
+            }
+
+            fn gen_nonce<R: rand::RngCore>(&self, nonce_rng: &mut R) -> NonceKeyPair {
+                ThresholdScheme::gen_nonce(&self.0, nonce_rng)
+            }
+
+            fn public_nonce(&self, secret_nonce: &NonceKeyPair) -> Nonce {
+                ThresholdScheme::public_nonce(&self.0, secret_nonce)
+            }
+
+            fn start_sign_session(
+                &self,
+                joint_key: &FrostKey<EvenY>,
+                nonces: Vec<(usize, Nonce)>,
+                message: &StoredMessage,
+            ) -> SignSession {
+                ThresholdScheme::start_sign_session(&self.0, joint_key, nonces, message)
+            }
+
+            fn sign(
+                &self,
+                joint_key: &FrostKey<EvenY>,
+                session: &SignSession,
+                my_index: usize,
+                secret_share: &Scalar,
+                secret_nonce: NonceKeyPair,
+            ) -> Scalar<Public, Zero> {
+                ThresholdScheme::sign(
+                    &self.0,
+                    joint_key,
+                    session,
+                    my_index,
+                    secret_share,
+                    secret_nonce,
+                )
+            }
+
+            fn verify_signature_share(
+                &self,
+                joint_key: &FrostKey<EvenY>,
+                session: &SignSession,
+                index: usize,
+                signature_share: Scalar<Public, Zero>,
+            ) -> bool {
+                ThresholdScheme::verify_signature_share(
+                    &self.0,
+                    joint_key,
+                    session,
+                    index,
+                    signature_share,
+                )
+            }
+
+            fn combine_signature_shares(
+                &self,
+                joint_key: &FrostKey<EvenY>,
+                session: &SignSession,
+                signature_shares: Vec<Scalar<Public, Zero>>,
+            ) -> Signature {
+                let mut signature = ThresholdScheme::combine_signature_shares(
+                    &self.0,
+                    joint_key,
+                    session,
+                    signature_shares,
+                );
+                signature.s = secp256kfun::s!(signature.s + 1).public();
+                signature
+            }
+
+            fn verify_signature(
+                &self,
+                joint_key: &FrostKey<EvenY>,
+                message: &StoredMessage,
+                signature: &Signature,
+            ) -> bool {
+                ThresholdScheme::verify_signature(&self.0, joint_key, message, signature)
+            }
+        }
+
+        let mut rng = rand::thread_rng();
+        let message = Message::plain("test", b"broken");
+        let group = Group::new(2, 3, &mut rng);
+        let scheme = BrokenCombine(group.frost.clone());
+
+        let events = Arc::new(Mutex::new(vec![]));
+        let observed = events.clone();
+        let roast = coordinator::Coordinator::new(scheme.clone(), group.joint_key.clone())
+            .with_observer(move |event: &CoordinatorEvent| {
+                observed.lock().unwrap().push(event.clone())
+            });
+        roast.start_session(message).unwrap();
+        // The signers use the broken scheme too, though it only differs in combining shares
+        let (mut signers, nonces): (Vec<_>, Vec<_>) = (0..2)
+            .map(|i| {
+                signer::RoastSigner::new(
+                    &mut rng,
+                    scheme.clone(),
+                    group.joint_key.clone(),
+                    i,
+                    group.secret_shares[i].clone(),
+                    message,
+                )
+            })
+            .unzip();
+        roast.receive(0, nonces[0].clone()).unwrap();
+        let sign_request = roast
+            .receive(1, nonces[1].clone())
+            .unwrap()
+            .message
+            .unwrap();
+        let replies: Vec<_> = signers
+            .iter_mut()
+            .map(|signer| {
+                signer
+                    .receive(&mut rng, sign_request.clone())
+                    .unwrap()
+                    .unwrap()
+            })
+            .collect();
+        roast.receive(0, replies[0].clone()).unwrap();
+
+        // Every share is valid, yet the signature they combine into is not
+        let (nonce_set, signature_shares) = match roast.receive(1, replies[1].clone()) {
+            Err(RoastError::InvalidSignature {
+                session_id: 1,
+                nonce_set,
+                signature_shares,
+            }) => (nonce_set, signature_shares),
+            other => panic!("expected an invalid signature, got {:?}", other),
+        };
+        let expected_nonces = match sign_request {
+            CoordinatorMessage::SignRequest { nonce_set, .. } => nonce_set,
+            other => panic!("expected a sign request, got {:?}", other),
+        };
+        assert_eq!(nonce_set.len(), 2);
+        assert!(nonce_set
+            .iter()
+            .all(|nonce| expected_nonces.contains(nonce)));
+        assert_eq!(
+            signature_shares,
+            vec![
+                (0, replies[0].signature_share().unwrap()),
+                (1, replies[1].signature_share().unwrap()),
+            ]
+        );
+        assert!(roast.malicious_signers().is_empty());
+        let seen = events.lock().unwrap().clone();
+        assert!(seen.contains(&CoordinatorEvent::SignatureRejected { session_id: 1 }));
+        assert!(!seen
+            .iter()
+            .any(|event| matches!(event, CoordinatorEvent::SignatureCombined { .. })));
+
+        // The failing share was recorded, so its retransmission is replayed without a sign
+        // request for the failed session, rather than being taken as a second share
+        assert_eq!(roast.receive(1, replies[1].clone()).unwrap().message, None);
+        assert!(roast.malicious_signers().is_empty());
+        assert_eq!(
+            events.lock().unwrap().last(),
+            Some(&CoordinatorEvent::RetransmissionReplayed { index: 1 })
+        );
+
+        // Both signers are responsive again, so a new session can be opened
+        let retry = roast.open_session().unwrap().unwrap();
+        assert!(matches!(
+            retry.message,
+            Some(CoordinatorMessage::SignRequest { session_id: 2, .. })
+        ));
+
+        // The failed session's nonces were handed out, so they may not be shared again
+        let copied = SignerMessage::NonceOnly {
+            message_id: nonces[0].message_id(),
+            nonce: nonces[0].nonce(),
+        };
+        assert_eq!(
+            roast.receive(2, copied).unwrap().message,
+            Some(CoordinatorMessage::Banned)
+        );
+    }
+
+    #[test]
+    fn coordinator_equivocation_is_detected_and_proven() {
+        use roast::announcement::{EquivocationProof, SchnorrAnnouncements, SessionAnnouncement};
+        use roast::protocol::message_id;
+
+        let mut rng = rand::thread_rng();
+
+        let group = Group::new(2, 3, &mut rng);
+        let coordinator_keypair = group.frost.schnorr.new_keypair(Scalar::random(&mut rng));
+        let coordinator_key = coordinator_keypair.public_key();
+
+        let new_signers = |rng: &mut rand::rngs::ThreadRng, message| {
+            let (signers, nonces) = group.signers(0..3, message, rng);
+            let signers: Vec<_> = signers
+                .into_iter()
+                .map(|signer| signer.with_coordinator_key(coordinator_key))
+                .collect();
+            (signers, nonces)
+        };
+
+        // An honest coordinator signs every sign request it sends
+        let message = Message::plain("test", b"announced");
+        let roast = group
+            .coordinator()
+            .with_announcement_key(coordinator_keypair.clone());
+        roast.start_session(message).unwrap();
+        let (mut signers, nonces) = new_signers(&mut rng, message);
+        roast.receive(0, nonces[0].clone()).unwrap();
+        let sign_request = roast
+            .receive(1, nonces[1].clone())
+            .unwrap()
+            .message
+            .unwrap();
+        assert!(matches!(
+            sign_request,
+            CoordinatorMessage::SignRequest {
+                announcement_signature: Some(_),
+                ..
+            }
+        ));
+        for (i, signer) in signers.iter_mut().enumerate().take(2) {
+            let reply = signer
+                .receive(&mut rng, sign_request.clone())
+                .unwrap()
+                .unwrap();
+            roast.receive(i, reply).unwrap();
+        }
+        assert!(signers[0].equivocation_proof().is_none());
+
+        // Signing the same message again carries on from session 1, so announcements passed on
+        // from the first signing never conflict with those of the second
+        let first_announcements = signers[0].announcements();
+        roast.start_session(message).unwrap();
+        let (mut signers, nonces) = new_signers(&mut rng, message);
+        roast.receive(0, nonces[0].clone()).unwrap();
+        let sign_request = roast
+            .receive(1, nonces[1].clone())
+            .unwrap()
+            .message
+            .unwrap();
+        assert!(matches!(
+            sign_request,
+            CoordinatorMessage::SignRequest { session_id: 2, .. }
+        ));
+        signers[0].receive(&mut rng, sign_request).unwrap();
+        for announcement in first_announcements {
+            assert_eq!(signers[0].check_announcement(announcement), Ok(()));
+        }
+        assert!(signers[0].equivocation_proof().is_none());
+
+        // A dishonest coordinator shows signers 0 and 2 different nonce sets for session 1
+        let message = Message::plain("test", b"equivocated");
+        let (mut signers, nonces) = new_signers(&mut rng, message);
+        let announce = |nonce_set: Vec<(usize, _)>| {
+            SessionAnnouncement::sign(
+                &SchnorrAnnouncements,
+                &coordinator_keypair,
+                message.into(),
+                1,
+                nonce_set,
+            )
+        };
+        let request = |announcement: &SessionAnnouncement| CoordinatorMessage::SignRequest {
+            message_id: message_id(&announcement.message),
+            session_id: announcement.session_id,
+            nonce_set: announcement.nonce_set.clone(),
+            announcement_signature: Some(announcement.signature.clone()),
+        };
+        let to_signer0 = announce(vec![(0, nonces[0].nonce()), (1, nonces[1].nonce())]);
+        let to_signer2 = announce(vec![(0, nonces[0].nonce()), (2, nonces[2].nonce())]);
+
+        // Requests must carry a valid announcement signature
+        let unsigned = CoordinatorMessage::SignRequest {
+            message_id: message_id(&to_signer0.message),
+            session_id: 1,
+            nonce_set: to_signer0.nonce_set.clone(),
+            announcement_signature: None,
+        };
+        assert_eq!(
+            signers[0].receive(&mut rng, unsigned),
+            Err(SignerError::InvalidAnnouncement)
+        );
+        let impostor = group.frost.schnorr.new_keypair(Scalar::random(&mut rng));
+        let forged = SessionAnnouncement::sign(
+            &SchnorrAnnouncements,
+            &impostor,
+            message.into(),
+            1,
+            to_signer0.nonce_set.clone(),
+        );
+        assert_eq!(
+            signers[0].receive(&mut rng, request(&forged)),
+            Err(SignerError::InvalidAnnouncement)
+        );
+
+        // Each signer signs what it was shown
+        assert!(signers[0]
+            .receive(&mut rng, request(&to_signer0))
+            .unwrap()
+            .is_some());
+        assert!(signers[2]
+            .receive(&mut rng, request(&to_signer2))
+            .unwrap()
+            .is_some());
+
+        // Passing announcements between signers reveals the equivocation
+        for announcement in signers[2].announcements() {
+            assert_eq!(
+                signers[0].check_announcement(announcement),
+                Err(SignerError::CoordinatorEquivocated)
+            );
+        }
+        let proof = signers[0].equivocation_proof().unwrap().clone();
+        let proof: EquivocationProof =
+            serde_json::from_str(&serde_json::to_string(&proof).unwrap()).unwrap();
+        assert!(proof.verify(&SchnorrAnnouncements, &coordinator_key));
+        assert!(!proof.verify(&SchnorrAnnouncements, &impostor.public_key()));
+
+        // Signer 0 refuses any further requests from the coordinator
+        let later = SessionAnnouncement::sign(
+            &SchnorrAnnouncements,
+            &coordinator_keypair,
+            message.into(),
+            2,
+            to_signer0.nonce_set,
+        );
+        assert_eq!(
+            signers[0].receive(&mut rng, request(&later)),
+            Err(SignerError::CoordinatorEquivocated)
+        );
+    }
+
+    #[test]
+    fn signer_manager_survives_dishonest_coordinators() {
+        use roast::manager::SignerManager;
+        use std::collections::VecDeque;
+        use std::time::Duration;
+
+        let mut rng = rand::thread_rng();
+
+        let group = Group::new(2, 4, &mut rng);
+        let message = Message::plain("test", b"many coordinators");
+
+        // n - t + 1 = 3 coordinators: 0 never replies, 1 announces a forged signature and 2 is honest
+        let stagger = Duration::from_millis(10);
+        let mut managers: Vec<_> = (0..4)
+            .map(|i| {
+                SignerManager::for_group(
+                    group.frost.clone(),
+                    group.joint_key.clone(),
+                    i,
+                    group.secret_shares[i].clone(),
+                    message,
+                    stagger,
+                )
+            })
+            .collect();
+        let forger = group.frost.schnorr.new_keypair(Scalar::random(&mut rng));
+        let forged_signature = group.frost.schnorr.sign(&forger, message);
+        let honest = group.coordinator();
+        honest.start_session(message).unwrap();
+
+        for step in 0..3 {
+            let elapsed = stagger * step;
+            let mut queue = VecDeque::new();
+            for (i, manager) in managers.iter_mut().enumerate() {
+                assert_eq!(
+                    manager.next_start(elapsed),
+                    Some(Duration::ZERO),
+                    "run {} is due",
+                    step
+                );
+                for (coordinator, nonce) in manager.start_due(&mut rng, elapsed) {
+                    queue.push_back((coordinator, i, nonce));
+                }
+            }
+            while let Some((coordinator, index, signer_message)) = queue.pop_front() {
+                let (recipients, reply) = match coordinator {
+                    0 => continue,
+                    1 => (
+                        vec![index],
+                        CoordinatorMessage::Completed {
+                            signature: forged_signature.clone(),
+                        },
+                    ),
+                    // Shares arriving after the signature was combined are rejected
+                    _ => match honest.receive(index, signer_message) {
+                        Ok(coordinator::RoastResponse {
+                            recipients,
+                            message: Some(reply),
+                        }) => (recipients, reply),
+                        _ => continue,
+                    },
+                };
+                for recipient in recipients {
+                    if let Some(signer_message) = managers[recipient]
+                        .receive(&mut rng, coordinator, reply.clone())
+                        .unwrap()
+                    {
+                        queue.push_back((coordinator, recipient, signer_message));
+                    }
+                }
+            }
+
+            if step == 1 {
+                // The forged signature is rejected and only that coordinator is abandoned
+                for manager in &managers {
+                    assert!(manager.signature().is_none());
+                    assert!(manager.is_running(0));
+                    assert!(!manager.is_running(1));
+                }
+            }
+        }
+
+        // The honest coordinator's signature shuts down every run
+        for manager in &mut managers {
+            let signature = manager
+                .signature()
+                .expect("signed by the honest coordinator");
+            assert!(group
+                .frost
+                .schnorr
+                .verify(&group.joint_key.public_key(), message, &signature));
+            assert!((0..3).all(|coordinator| !manager.is_running(coordinator)));
+            assert_eq!(manager.next_start(Duration::ZERO), None);
+            assert!(manager
+                .receive(&mut rng, 0, CoordinatorMessage::Banned)
+                .unwrap()
+                .is_none());
+        }
+    }
+
+    #[test]
+    fn selection_prefers_fast_signers_and_opens_sessions_on_demand() {
+        use roast::coordinator::SignerRecord;
+        use roast::selection::{FastestSigners, MostReliable, Randomised, SignerSelection};
+        use std::collections::{HashMap, HashSet};
+
+        let mut rng = rand::thread_rng();
+
+        let group = Group::new(2, 4, &mut rng);
+        let roast = group
+            .coordinator()
+            .with_selection(FastestSigners { pool_size: 3 });
+
+        let sign_request_signers = |message: &Option<CoordinatorMessage>| match message {
+            Some(CoordinatorMessage::SignRequest { nonce_set, .. }) => {
+                nonce_set.iter().map(|(i, _)| *i).collect::<HashSet<_>>()
+            }
+            _ => panic!("expected a sign request"),
+        };
+
+        let message = Message::plain("test", b"first");
+        roast.start_session(message).unwrap();
+        let (mut signers, nonces) = group.signers(0..2, message, &mut rng);
+
+        // The pool of three is never filled, so the session is opened on demand
+        for (i, nonce) in nonces.into_iter().enumerate() {
+            assert!(roast.receive(i, nonce).unwrap().message.is_none());
+        }
+        let response = roast
+            .open_session()
+            .unwrap()
+            .expect("two signers responsive");
+        assert_eq!(
+            sign_request_signers(&response.message),
+            HashSet::from([0, 1])
+        );
+        assert!(roast.open_session().unwrap().is_none());
+
+        // Signer 1 answers before signer 0
+        let sign_request = response.message.unwrap();
+        let share1 = signers[1]
+            .receive(&mut rng, sign_request.clone())
+            .unwrap()
+            .unwrap();
+        let share0 = signers[0].receive(&mut rng, sign_request).unwrap().unwrap();
+        assert!(roast.receive(1, share1).unwrap().message.is_none());
+        assert!(matches!(
+            roast.receive(0, share0).unwrap().message,
+            Some(CoordinatorMessage::Completed { .. })
+        ));
+        assert_eq!(roast.signer_record(1).average_share_position(), Some(0.0));
+        assert_eq!(roast.signer_record(0).average_share_position(), Some(1.0));
+
+        // With a full pool the fastest signers are chosen and the slowest stays responsive
+        let message = Message::plain("test", b"second");
+        roast.start_session(message).unwrap();
+        let (mut signers, nonces) = group.signers(0..4, message, &mut rng);
+        let mut nonces: Vec<_> = nonces.into_iter().map(Some).collect();
+        for i in [2, 0] {
+            let nonce = nonces[i].take().unwrap();
+            assert!(roast.receive(i, nonce).unwrap().message.is_none());
+        }
+        let response = roast.receive(1, nonces[1].take().unwrap()).unwrap();
+        assert_eq!(
+            sign_request_signers(&response.message),
+            HashSet::from([0, 1])
+        );
+        let sign_request = response.message.unwrap();
+
+        // Signers 2 and 3 do not fill the pool alone
+        let response = roast.receive(3, nonces[3].take().unwrap()).unwrap();
+        assert!(response.message.is_none());
+        let response = roast
+            .open_session()
+            .unwrap()
+            .expect("two signers responsive");
+        assert_eq!(
+            sign_request_signers(&response.message),
+            HashSet::from([2, 3])
+        );
+
+        let mut signature = None;
+        for i in [0, 1] {
+            let share = signers[i]
+                .receive(&mut rng, sign_request.clone())
+                .unwrap()
+                .unwrap();
+            if let Some(CoordinatorMessage::Completed { signature: sig }) =
+                roast.receive(i, share).unwrap().message
+            {
+                signature = Some(sig);
+            }
+        }
+        assert!(group.frost.schnorr.verify(
+            &group.joint_key.public_key(),
+            message,
+            &signature.expect("signed")
+        ));
+
+        // Signers which complete their sessions are preferred, and unknown signers are trusted
+        let records = HashMap::from([
+            (
+                0,
+                SignerRecord {
+                    sessions_joined: 4,
+                    shares_accepted: 1,
+                    ..Default::default()
+                },
+            ),
+            (
+                1,
+                SignerRecord {
+                    sessions_joined: 4,
+                    shares_accepted: 4,
+                    ..Default::default()
+                },
+            ),
+        ]);
+        let mut most_reliable = MostReliable { pool_size: 3 };
+        assert_eq!(most_reliable.select(&[0, 1, 2], 2, &records), vec![1, 2]);
+
+        // Random choices are distinct candidates and reproducible from the seed
+        let choose = || Randomised::new(4, 7).select(&[0, 2, 4, 6], 2, &records);
+        let chosen = choose();
+        assert_eq!(chosen.len(), 2);
+        assert_ne!(chosen[0], chosen[1]);
+        assert!(chosen.iter().all(|i| [0, 2, 4, 6].contains(i)));
+        assert_eq!(chosen, choose());
+    }
+
+    #[test]
+    fn simulator_replays_asynchronous_runs() {
+        use rand::{rngs::StdRng, SeedableRng};
+        use roast::link::{Latency, Link};
+        use roast::simulator::Simulation;
+
+        let group = Group::new(3, 6, &mut rand::thread_rng());
+        let message = Message::plain("test", b"simulated");
+
+        let simulate = |seed: u64, link: Link| {
+            let roast = group.coordinator();
+            roast.start_session(message).unwrap();
+            // The signers' first nonces must not come from the simulation's own seed
+            let mut rng = StdRng::seed_from_u64(seed + 1000);
+            // Signer 5 is absent
+            let (signers, nonces) = group.signers(0..5, message, &mut rng);
+            let signers = signers.into_iter().zip(nonces).collect();
+            // Signer 0 is far slower than the rest
+            let slow = Link {
+                latency: Latency::Fixed(500),
+                ..link
+            };
+            Simulation::new(roast, signers, seed)
+                .with_link(link)
+                .with_signer_links(0, slow, slow)
+                .with_time_limit(10_000)
+                .run()
+        };
+
+        let jittery = Link {
+            latency: Latency::Uniform { min: 1, max: 50 },
+            drop_rate: 0.0,
+            reorder: true,
+        };
+        let report = simulate(7, jittery);
+        assert_eq!(report, simulate(7, jittery));
+        let signature = report.signature.expect("signed without losses");
+        assert!(group
+            .frost
+            .schnorr
+            .verify(&group.joint_key.public_key(), message, &signature));
+        assert!(report.sessions_opened >= 1);
+        assert!(report.time_to_signature.unwrap() < 500);
+        assert_eq!(report.messages_dropped, 0);
+
+        // Lost messages are counted, and the run ends once nothing is left in flight
+        let lossy = Link {
+            latency: Latency::Exponential { mean: 20.0 },
+            drop_rate: 0.2,
+            reorder: true,
+        };
+        let report = simulate(11, lossy);
+        assert_eq!(report, simulate(11, lossy));
+        assert!(report.messages_dropped > 0);
+        assert!(report.messages_dropped < report.messages_sent);
+        if let Some(signature) = report.signature {
+            assert!(group
+                .frost
+                .schnorr
+                .verify(&group.joint_key.public_key(), message, &signature));
+        }
+    }
+
+    #[test]
+    fn absent_signers_never_stall_a_pool_larger_than_the_threshold() {
+        use roast::selection::MostReliable;
+        use roast::simulator::Simulation;
+
+        let mut rng = rand::thread_rng();
+        let group = Group::new(3, 6, &mut rng);
+        let message = Message::plain("test", b"idle");
+        // The pool waits for five signers, yet only three show up
+        let roast = group
+            .coordinator()
+            .with_selection(MostReliable { pool_size: 5 });
+        roast.start_session(message).unwrap();
+        let (signers, nonces) = group.signers([0, 2, 4], message, &mut rng);
+        let signers = signers.into_iter().zip(nonces).collect();
+
+        let report = Simulation::new(roast, signers, 3)
+            .with_idle_timeout(50)
+            .run();
+        let signature = report.signature.expect("signed by the signers present");
+        assert!(group
+            .frost
+            .schnorr
+            .verify(&group.joint_key.public_key(), message, &signature));
+        assert_eq!(report.sessions_opened, 1);
+        assert!(report.time_to_signature.unwrap() >= 50);
+        // Three nonces, three sign requests, three shares and the signature sent to all six
+        assert_eq!(report.messages_sent, 15);
+    }
+
+    #[test]
+    fn adversaries_never_need_more_than_n_minus_t_plus_one_sessions() {
+        use rand::SeedableRng;
+        use rand_chacha::ChaCha20Rng;
+        use roast::adversary::Adversary;
+        use roast::link::{Latency, Link};
+        use roast::simulator::Simulation;
+
+        let mut rng = ChaCha20Rng::seed_from_u64(42);
+        // Both points of the nonce are the generator, so its secrets are known to everyone
+        let generator_nonce = NonceKeyPair::from_secrets([Scalar::one(), Scalar::one()]).public();
+        let adversaries = vec![
+            Adversary::Withhold,
+            Adversary::RespondLate(300),
+            Adversary::InvalidShare(Scalar::random(&mut rng).mark_zero().public()),
+            Adversary::Unsolicited,
+            Adversary::WrongSession,
+            Adversary::ReuseNonce,
+            Adversary::ForeignNonce(generator_nonce),
+            Adversary::SilentAfterNonce,
+        ];
+
+        let simulate = |threshold: usize,
+                        n_signers: usize,
+                        adversaries: &[Adversary],
+                        seed: u64| {
+            let mut rng = ChaCha20Rng::seed_from_u64(seed);
+            let message = Message::plain("test", b"adversaries");
+            let (group, roast, signers, nonces) = setup(threshold, n_signers, message, &mut rng);
+            let signers = signers.into_iter().zip(nonces).collect();
+            let mut simulation = Simulation::new(roast, signers, seed).with_link(Link {
+                latency: Latency::Uniform { min: 1, max: 50 },
+                drop_rate: 0.0,
+                reorder: true,
+            });
+            // Spread the adversaries among the honest signers
+            let mut indices: Vec<usize> = (0..n_signers).collect();
+            indices.shuffle(&mut rng);
+            for (index, adversary) in indices.into_iter().zip(adversaries) {
+                simulation = simulation.with_adversary(index, adversary.clone());
+            }
+            let report = simulation.run();
+            let signature = report.signature.unwrap_or_else(|| {
+                panic!("{:?} prevented signing", adversaries);
+            });
+            assert!(group
+                .frost
+                .schnorr
+                .verify(&group.joint_key.public_key(), message, &signature));
+            assert!(
+                report.sessions_opened <= n_signers - threshold + 1,
+                "{:?} needed {} sessions",
+                adversaries,
+                report.sessions_opened
+            );
+        };
+
+        // As many adversaries of each kind as can be tolerated
+        for (seed, adversary) in adversaries.iter().enumerate() {
+            simulate(3, 7, &vec![adversary.clone(); 4], seed as u64);
+        }
+        // And every kind at once
+        for seed in 0..3 {
+            simulate(3, 11, &adversaries, seed);
+        }
+    }
+
+    #[test]
+    fn faulty_transport_duplicates_never_ban_honest_signers() {
+        use roast::link::{Latency, Link};
+        use roast::transport::{FaultyTransport, Transport};
+
+        let mut rng = rand::thread_rng();
+        let group = Group::new(3, 5, &mut rng);
+        let message = Message::plain("test", b"faulty transport");
+
+        let link = Link {
+            latency: Latency::Exponential { mean: 20.0 },
+            drop_rate: 0.0,
+            reorder: true,
+        };
+        let slow = Link {
+            latency: Latency::Fixed(200),
+            ..link
+        };
+        let mut duplicates_handled = 0;
+        for seed in 0..10 {
+            let events = Arc::new(Mutex::new(vec![]));
+            let observed = events.clone();
+            let roast = group
+                .coordinator()
+                .with_observer(move |event: &CoordinatorEvent| {
+                    observed.lock().unwrap().push(event.clone())
+                });
+            roast.start_session(message).unwrap();
+
+            // Half of all messages arrive twice, in any order, signer 0 is slow to reply and
+            // signer 4 is cut off for a while
+            let mut uplink = FaultyTransport::new(vec![], link, seed)
+                .with_duplicate_rate(0.5)
+                .with_partition([4], 0, 100);
+            let mut downlink = FaultyTransport::new(vec![], link, seed + 1000)
+                .with_peer_link(0, slow)
+                .with_duplicate_rate(0.5)
+                .with_partition([4], 0, 100);
+            let (mut signers, nonces) = group.signers(0..5, message, &mut rng);
+            for (i, nonce) in nonces.into_iter().enumerate() {
+                uplink.send(i, nonce);
+            }
+
+            while roast.result().is_none() && uplink.now() < 10_000 {
+                uplink.advance(1);
+                for (index, signer_message) in std::mem::take(uplink.inner_mut()) {
+                    let response = match roast.receive(index, signer_message) {
+                        Ok(response) => response,
+                        Err(_) => continue,
+                    };
+                    if let Some(coordinator_message) = response.message {
+                        for recipient in response.recipients {
+                            downlink.send(recipient, coordinator_message.clone());
+                        }
+                    }
+                }
+                downlink.advance(1);
+                for (index, coordinator_message) in std::mem::take(downlink.inner_mut()) {
+                    // A duplicated sign request is refused, as its nonce has been used
+                    if let Ok(Some(reply)) = signers[index].receive(&mut rng, coordinator_message) {
+                        uplink.send(index, reply);
+                    }
+                }
+            }
+
+            let signature = roast.result().expect("signed despite the faults");
+            assert!(group
+                .frost
+                .schnorr
+                .verify(&group.joint_key.public_key(), message, &signature));
+            assert!(roast.malicious_signers().is_empty());
+            duplicates_handled += events
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|event| {
+                    matches!(
+                        event,
+                        CoordinatorEvent::RetransmissionReplayed { .. }
+                            | CoordinatorEvent::DuplicateIgnored { .. }
+                    )
+                })
+                .count();
+        }
+        assert!(duplicates_handled > 0);
+    }
+
+    #[test]
+    fn messages_for_another_message_are_rejected() {
+        use roast::link::{Latency, Link};
+        use roast::transport::{FaultyTransport, Transport};
+
+        let mut rng = rand::thread_rng();
+        let group = Group::new(2, 3, &mut rng);
+        let first = Message::plain("test", b"first");
+        let second = Message::plain("test", b"second");
+        let roast = group.coordinator();
+        roast.start_session(first).unwrap();
+
+        // Signer 0's nonce for the first message is held up until the second is being signed
+        let link = Link {
+            latency: Latency::Fixed(1),
+            drop_rate: 0.0,
+            reorder: false,
+        };
+        let slow = Link {
+            latency: Latency::Fixed(100),
+            ..link
+        };
+        let mut uplink = FaultyTransport::new(vec![], link, 0).with_peer_link(0, slow);
+        let (mut first_signers, nonces) = group.signers(0..3, first, &mut rng);
+        for (i, nonce) in nonces.into_iter().enumerate() {
+            uplink.send(i, nonce);
+        }
+        uplink.advance(1);
+        let mut first_request = None;
+        for (index, signer_message) in std::mem::take(uplink.inner_mut()) {
+            first_request = roast.receive(index, signer_message).unwrap().message;
+        }
+        let first_request = first_request.expect("signers 1 and 2 asked to sign");
+        for i in [1, 2] {
+            let reply = first_signers[i]
+                .receive(&mut rng, first_request.clone())
+                .unwrap()
+                .unwrap();
+            roast.receive(i, reply).unwrap();
+        }
+        assert!(roast.result().is_some());
+
+        roast.start_session(second).unwrap();
+        uplink.advance(100);
+        let (index, late_nonce) = uplink.inner_mut().pop().expect("late nonce delivered");
+        assert_eq!(
+            roast.receive(index, late_nonce),
+            Err(RoastError::WrongMessage(0))
+        );
+
+        // Signer 0's fresh nonce is still accepted, and a signer only signs for its own message
+        let (mut signers, nonces) = group.signers(0..2, second, &mut rng);
+        roast.receive(0, nonces[0].clone()).unwrap();
+        let request = roast
+            .receive(1, nonces[1].clone())
+            .unwrap()
+            .message
+            .unwrap();
+        assert_eq!(
+            first_signers[0].receive(&mut rng, request.clone()),
+            Err(SignerError::WrongMessage)
+        );
+        for i in [0, 1] {
+            let reply = signers[i]
+                .receive(&mut rng, request.clone())
+                .unwrap()
+                .unwrap();
+            roast.receive(i, reply).unwrap();
+        }
+        let signature = roast.result().expect("second message signed");
+        assert!(group
+            .frost
+            .schnorr
+            .verify(&group.joint_key.public_key(), second, &signature));
+        assert!(roast.malicious_signers().is_empty());
+    }
+
+    #[test]
+    fn coordinator_hosts_other_threshold_schemes() {
+        use roast::threshold_scheme::ThresholdScheme;
+
+        /// An insecure scheme over integers whose shares anyone can check, as the key reveals the
+        /// secret shares
+        #[derive(Clone)]
+        struct Toy;
+
+        #[derive(Clone)]
+        struct ToyKey {
+            threshold: usize,
+            shares: Vec<u64>,
+        }
+
+        fn toy_share(key: &ToyKey, nonces: &[(usize, u64)], index: usize, message: &str) -> u64 {
+            let nonce = nonces.iter().find(|(i, _)| *i == index).unwrap().1;
+            let binding = nonces
+                .iter()
+                .map(|(i, x)| x ^ *i as u64)
+                .chain(message.bytes().map(u64::from))
+                .fold(0u64, |acc, x| acc.wrapping_mul(31).wrapping_add(x));
+            key.shares[index].wrapping_mul(binding).wrapping_add(nonce)
+        }
+
+        impl ThresholdScheme<ToyKey> for Toy {
+            type PublicNonce = u64;
+            type SecretNonce = u64;
+            type SecretShare = u64;
+            type SignatureShare = u64;
+            type Signature = (Vec<(usize, u64)>, u64);
+            type Message = String;
+            type SignSession = (Vec<(usize, u64)>, String);
+
+            fn threshold(&self, joint_key: &ToyKey) -> usize {
+                joint_key.threshold
+            }
+
+            fn n_signers(&self, joint_key: &ToyKey) -> usize {
+                joint_key.shares.len()
+            }
+
+            fn gen_nonce<R: rand::RngCore>(&self, nonce_rng: &mut R) -> u64 {
+                nonce_rng.next_u64()
+            }
+
+            fn public_nonce(&self, secret_nonce: &u64) -> u64 {
+                *secret_nonce
+            }
+
+            fn start_sign_session(
+                &self,
+                _joint_key: &ToyKey,
+                nonces: Vec<(usize, u64)>,
+                message: &String,
+            ) -> (Vec<(usize, u64)>, String) {
+                (nonces, message.clone())
+            }
+
+            fn sign(
+                &self,
+                joint_key: &ToyKey,
+                (nonces, message): &(Vec<(usize, u64)>, String),
+                my_index: usize,
+                _secret_share: &u64,
+                _secret_nonce: u64,
+            ) -> u64 {
+                toy_share(joint_key, nonces, my_index, message)
+            }
+
+            fn verify_signature_share(
+                &self,
+                joint_key: &ToyKey,
+                (nonces, message): &(Vec<(usize, u64)>, String),
+                index: usize,
+                signature_share: u64,
+            ) -> bool {
+                toy_share(joint_key, nonces, index, message) == signature_share
+            }
+
+            fn combine_signature_shares(
+                &self,
+                _joint_key: &ToyKey,
+                (nonces, _message): &(Vec<(usize, u64)>, String),
+                signature_shares: Vec<u64>,
+            ) -> (Vec<(usize, u64)>, u64) {
+                let sum = signature_shares
+                    .into_iter()
+                    .fold(0u64, |acc, share| acc.wrapping_add(share));
+                (nonces.clone(), sum)
+            }
+
+            fn verify_signature(
+                &self,
+                joint_key: &ToyKey,
+                message: &String,
+                signature: &(Vec<(usize, u64)>, u64),
+            ) -> bool {
+                let (nonces, sum) = signature;
+                nonces.len() >= joint_key.threshold
+                    && nonces.iter().fold(0u64, |acc, (i, _)| {
+                        acc.wrapping_add(toy_share(joint_key, nonces, *i, message))
+                    }) == *sum
+            }
+        }
+
+        let mut rng = rand::thread_rng();
+        let joint_key = ToyKey {
+            threshold: 2,
+            shares: vec![3, 5, 7],
+        };
+        let message = String::from("toy message");
+        let roast = coordinator::Coordinator::new(Toy, joint_key.clone());
+        roast.start_session(message.clone()).unwrap();
+
+        let mut signers = vec![];
+        let mut queue = std::collections::VecDeque::new();
+        for (i, share) in joint_key.shares.iter().enumerate() {
+            let (signer, nonce) = signer::RoastSigner::new(
+                &mut rng,
+                Toy,
+                joint_key.clone(),
+                i,
+                *share,
+                message.clone(),
+            );
+            signers.push(signer);
+            queue.push_back((i, nonce));
+        }
+
+        // Signer 0 tampers with its signature share
+        let mut signature = None;
+        while let Some((index, signer_message)) = queue.pop_front() {
+            let signer_message = match signer_message {
+                SignerMessage::ShareAndNonce {
+                    message_id,
+                    session_id,
+                    signature_share,
+                    nonce,
+                } if index == 0 => SignerMessage::ShareAndNonce {
+                    message_id,
+                    session_id,
+                    signature_share: signature_share.wrapping_add(1),
+                    nonce,
+                },
+                signer_message => signer_message,
+            };
+            let response = roast.receive(index, signer_message).unwrap();
+            let reply = match response.message {
+                Some(CoordinatorMessage::Completed { signature: sig }) => {
+                    signature = Some(sig);
+                    break;
+                }
+                Some(reply) => reply,
+                None => continue,
+            };
+            for recipient in response.recipients {
+                if let Some(signer_message) =
+                    signers[recipient].receive(&mut rng, reply.clone()).unwrap()
+                {
+                    queue.push_back((recipient, signer_message));
+                }
+            }
+        }
+
+        assert!(Toy.verify_signature(&joint_key, &message, &signature.expect("signed")));
+        assert_eq!(roast.malicious_signers(), [0].into());
+        assert!(verify_blame_proof(&Toy, &roast.blame_proof(0).unwrap()));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn coordinator_binary_signs_over_a_socket() {
+        use roast::wire::{
+            read_frame, write_frame, CoordinatorFrame, Endpoint, SignerFrame, Stream,
+        };
+        use std::io::{BufRead, BufReader, Write};
+        use std::process::{Command, Stdio};
+
+        let mut rng = rand::thread_rng();
+
+        let group = Group::new(2, 3, &mut rng);
+        let dir = std::env::temp_dir().join(format!("roast-{}", rand::random::<u64>()));
+        std::fs::create_dir(&dir).unwrap();
+        let config = serde_json::json!({ "joint_key": group.joint_key });
+        std::fs::write(dir.join("group.json"), config.to_string()).unwrap();
+        let endpoint: Endpoint = format!("unix:{}", dir.join("roast.sock").display())
+            .parse()
+            .unwrap();
+
+        let mut coordinator = Command::new(env!("CARGO_BIN_EXE_roast-coordinator"))
+            .arg(dir.join("group.json"))
+            .arg(endpoint.to_string())
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap();
+
+        let signers: Vec<_> = (0..2)
+            .map(|index| {
+                let frost = group.frost.clone();
+                let joint_key = group.joint_key.clone();
+                let secret_share = group.secret_shares[index].clone();
+                let endpoint = endpoint.clone();
+                std::thread::spawn(move || {
+                    let mut rng = rand::thread_rng();
+                    let mut stream: Stream = (0..100)
+                        .find_map(|_| {
+                            endpoint.connect().ok().or_else(|| {
+                                std::thread::sleep(std::time::Duration::from_millis(50));
+                                None
+                            })
+                        })
+                        .expect("coordinator is listening");
+                    write_frame(&mut stream, &SignerFrame::Hello { index }).unwrap();
+                    let message = match read_frame(&mut stream).unwrap() {
+                        CoordinatorFrame::Sign { message } => message,
+                        frame => panic!("expected a message to sign, got {:?}", frame),
+                    };
+                    let (mut signer, nonce) = signer::RoastSigner::new(
+                        &mut rng,
+                        frost,
+                        joint_key,
+                        index,
+                        secret_share,
+                        message.as_message(),
+                    );
+                    write_frame(&mut stream, &SignerFrame::Message(nonce)).unwrap();
+                    while signer.signature().is_none() {
+                        let message = match read_frame(&mut stream).unwrap() {
+                            CoordinatorFrame::Message(message) => message,
+                            frame => panic!("unexpected frame {:?}", frame),
+                        };
+                        if let Some(reply) = signer.receive(&mut rng, message).unwrap() {
+                            write_frame(&mut stream, &SignerFrame::Message(reply)).unwrap();
+                        }
+                    }
+                    signer.signature().unwrap()
+                })
+            })
+            .collect();
+
+        // Give both signers the chance to connect before the message is announced, though
+        // signers connecting later are told about it too
+        std::thread::sleep(std::time::Duration::from_millis(200));
+        let message = b"sign me over a socket";
+        writeln!(
+            coordinator.stdin.as_mut().unwrap(),
+            "{}",
+            secp256kfun::hex::encode(message)
+        )
+        .unwrap();
+        let mut line = String::new();
+        BufReader::new(coordinator.stdout.as_mut().unwrap())
+            .read_line(&mut line)
+            .unwrap();
+        let signature: schnorr_fun::Signature = serde_json::from_str(&line).unwrap();
+        assert!(group.frost.schnorr.verify(
+            &group.joint_key.public_key(),
+            Message::<secp256kfun::marker::Public>::raw(message),
+            &signature
+        ));
+        for signer in signers {
+            assert_eq!(signer.join().unwrap(), signature);
+        }
+
+        coordinator.kill().unwrap();
+        coordinator.wait().unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn coordinator_binary_drops_frames_for_earlier_messages() {
+        use roast::wire::{
+            read_frame, write_frame, CoordinatorFrame, Endpoint, SignerFrame, Stream,
+        };
+        use std::io::{BufRead, BufReader, Write};
+        use std::process::{Command, Stdio};
+
+        /// Read frames until `f` picks one out
+        fn next<T>(stream: &mut Stream, mut f: impl FnMut(CoordinatorFrame) -> Option<T>) -> T {
+            loop {
+                if let Some(picked) = f(read_frame(stream).unwrap()) {
+                    return picked;
+                }
+            }
+        }
+
+        let mut rng = rand::thread_rng();
+        let group = Group::new(2, 3, &mut rng);
+        let dir = std::env::temp_dir().join(format!("roast-{}", rand::random::<u64>()));
+        std::fs::create_dir(&dir).unwrap();
+        let config = serde_json::json!({ "joint_key": group.joint_key });
+        std::fs::write(dir.join("group.json"), config.to_string()).unwrap();
+        let endpoint: Endpoint = format!("unix:{}", dir.join("roast.sock").display())
+            .parse()
+            .unwrap();
+        let mut coordinator = Command::new(env!("CARGO_BIN_EXE_roast-coordinator"))
+            .arg(dir.join("group.json"))
+            .arg(endpoint.to_string())
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap();
+        let mut stdin = coordinator.stdin.take().unwrap();
+        let mut stdout = BufReader::new(coordinator.stdout.take().unwrap());
+
+        let mut streams: Vec<Stream> = (0..3)
+            .map(|index| {
+                let mut stream = (0..100)
+                    .find_map(|_| {
+                        endpoint.connect().ok().or_else(|| {
+                            std::thread::sleep(std::time::Duration::from_millis(50));
+                            None
+                        })
+                    })
+                    .expect("coordinator is listening");
+                write_frame(&mut stream, &SignerFrame::Hello { index }).unwrap();
+                stream
+            })
+            .collect();
+
+        // Sign a message with the signers at `indices`, after sending any `late` frame, returning the
+        // nonce the remaining signer would have sent
+        let mut sign = |streams: &mut [Stream],
+                        bytes: &[u8],
+                        indices: [usize; 2],
+                        late: Option<(usize, SignerFrame)>| {
+            writeln!(stdin, "{}", secp256kfun::hex::encode(bytes)).unwrap();
+            let message = Message::<secp256kfun::marker::Public>::raw(bytes);
+            for stream in streams.iter_mut() {
+                next(stream, |frame| match frame {
+                    CoordinatorFrame::Sign { message } => Some(message),
+                    _ => None,
+                });
+            }
+            let (mut signers, nonces) = group.signers(0..3, message, &mut rand::thread_rng());
+            if let Some((index, late)) = late {
+                write_frame(&mut streams[index], &late).unwrap();
+            }
+            for i in indices {
+                write_frame(&mut streams[i], &SignerFrame::Message(nonces[i].clone())).unwrap();
+            }
+            for i in indices {
+                let sign_request = next(&mut streams[i], |frame| match frame {
+                    CoordinatorFrame::Message(CoordinatorMessage::Banned) => {
+                        panic!("signer {} was banned", i)
+                    }
+                    CoordinatorFrame::Message(
+                        sign_request @ CoordinatorMessage::SignRequest { .. },
+                    ) => Some(sign_request),
+                    _ => None,
+                });
+                let reply = signers[i]
+                    .receive(&mut rand::thread_rng(), sign_request)
+                    .unwrap()
+                    .unwrap();
+                write_frame(&mut streams[i], &SignerFrame::Message(reply)).unwrap();
+            }
+            let mut line = String::new();
+            stdout.read_line(&mut line).unwrap();
+            let signature: schnorr_fun::Signature = serde_json::from_str(&line).unwrap();
+            assert!(group
+                .frost
+                .schnorr
+                .verify(&group.joint_key.public_key(), message, &signature));
+            let remaining = (0..3).find(|i| !indices.contains(i)).unwrap();
+            SignerFrame::Message(nonces[remaining].clone())
+        };
+
+        // Signer 0's nonce for the first message only arrives once the second is being signed, where
+        // it would be taken for its nonce and get its real nonce banned as unsolicited
+        let late = sign(&mut streams, b"first", [1, 2], None);
+        sign(&mut streams, b"second", [0, 1], Some((0, late)));
+
+        coordinator.kill().unwrap();
+        coordinator.wait().unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn signer_daemons_reconnect_and_sign() {
+        use std::io::{BufRead, BufReader, Write};
+        use std::process::{Command, Stdio};
+
+        let mut rng = rand::thread_rng();
+
+        let group = Group::new(2, 3, &mut rng);
+        let dir = std::env::temp_dir().join(format!("roast-{}", rand::random::<u64>()));
+        std::fs::create_dir(&dir).unwrap();
+        let config = serde_json::json!({ "joint_key": group.joint_key });
+        std::fs::write(dir.join("group.json"), config.to_string()).unwrap();
+        let endpoint = format!("unix:{}", dir.join("roast.sock").display());
+
+        // The signers are started first and keep retrying until the coordinator is up
+        let mut signers: Vec<_> = (0..2)
+            .map(|index| {
+                let config = serde_json::json!({
+                    "joint_key": group.joint_key,
+                    "index": index,
+                    "secret_share": group.secret_shares[index],
+                });
+                let config_path = dir.join(format!("signer-{}.json", index));
+                std::fs::write(&config_path, config.to_string()).unwrap();
+                Command::new(env!("CARGO_BIN_EXE_roast-signer"))
+                    .arg(config_path)
+                    .arg(&endpoint)
+                    .stdout(Stdio::piped())
+                    .stderr(Stdio::null())
+                    .spawn()
+                    .unwrap()
+            })
+            .collect();
+        std::thread::sleep(std::time::Duration::from_millis(300));
+
+        let mut coordinator = Command::new(env!("CARGO_BIN_EXE_roast-coordinator"))
+            .arg(dir.join("group.json"))
+            .arg(&endpoint)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap();
+        let message = b"signed by daemons";
+        writeln!(
+            coordinator.stdin.as_mut().unwrap(),
+            "{}",
+            secp256kfun::hex::encode(message)
+        )
+        .unwrap();
+
+        let mut line = String::new();
+        BufReader::new(coordinator.stdout.as_mut().unwrap())
+            .read_line(&mut line)
+            .unwrap();
+        let signature: schnorr_fun::Signature = serde_json::from_str(&line).unwrap();
+        assert!(group.frost.schnorr.verify(
+            &group.joint_key.public_key(),
+            Message::<secp256kfun::marker::Public>::raw(message),
+            &signature
+        ));
+
+        // Every signer learns the signature too
+        for signer in &mut signers {
+            let mut line = String::new();
+            BufReader::new(signer.stdout.as_mut().unwrap())
+                .read_line(&mut line)
+                .unwrap();
+            assert_eq!(
+                serde_json::from_str::<schnorr_fun::Signature>(&line).unwrap(),
+                signature
+            );
+        }
+
+        // A coordinator restarted without its state has no record of the signers' shares, so they
+        // rejoin with fresh nonces when it asks for the message again
+        coordinator.kill().unwrap();
+        coordinator.wait().unwrap();
+        std::fs::remove_file(dir.join("roast.sock")).unwrap();
+        let mut coordinator = Command::new(env!("CARGO_BIN_EXE_roast-coordinator"))
+            .arg(dir.join("group.json"))
+            .arg(&endpoint)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap();
+        writeln!(
+            coordinator.stdin.as_mut().unwrap(),
+            "{}",
+            secp256kfun::hex::encode(message)
+        )
+        .unwrap();
+        let mut line = String::new();
+        BufReader::new(coordinator.stdout.as_mut().unwrap())
+            .read_line(&mut line)
+            .unwrap();
+        let signature: schnorr_fun::Signature = serde_json::from_str(&line).unwrap();
+        assert!(group.frost.schnorr.verify(
+            &group.joint_key.public_key(),
+            Message::<secp256kfun::marker::Public>::raw(message),
+            &signature
+        ));
+
+        for mut process in signers.into_iter().chain([coordinator]) {
+            process.kill().unwrap();
+            process.wait().unwrap();
+        }
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn async_driver_signs_with_absent_signers() {
+        let mut rng = rand::thread_rng();
+
+        let group = Group::new(3, 5, &mut rng);
+        let message = Message::plain("test", b"async");
+        let roast = group.coordinator();
+        roast.start_session(message).unwrap();
+
+        // Signers 1 and 3 never show up
+        let (signers, nonces) = group.signers([0, 2, 4], message, &mut rng);
+        let signers = signers.into_iter().zip(nonces).collect();
+
+        let signature = roast::driver::sign(&roast, signers).await.unwrap();
+        assert!(group
+            .frost
+            .schnorr
+            .verify(&group.joint_key.public_key(), message, &signature));
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn async_driver_opens_sessions_when_pool_signers_are_absent() {
+        use roast::selection::MostReliable;
+
+        let mut rng = rand::thread_rng();
+
+        let group = Group::new(3, 6, &mut rng);
+        let message = Message::plain("test", b"async idle");
+        let roast = group
+            .coordinator()
+            .with_selection(MostReliable { pool_size: 5 });
+        roast.start_session(message).unwrap();
+
+        // Only three of the five signers the pool waits for show up
+        let (signers, nonces) = group.signers([1, 3, 5], message, &mut rng);
+        let signers = signers.into_iter().zip(nonces).collect();
+
+        let signature = roast::driver::sign(&roast, signers).await.unwrap();
+        assert!(group
+            .frost
+            .schnorr
+            .verify(&group.joint_key.public_key(), message, &signature));
     }
 
     // This test works, but slowly since it goes through a few sets of responsive signers
     // before producing a complete signature. This is because we aren't accurately replicating
-    // any asynchronous messages.
+    // any asynchronous messages, which `roast::simulator` does.
     fn t_of_n_sequential(
         threshold: usize,
         n_parties: usize,
@@ -100,19 +2572,9 @@ mod tests {
             "Testing {}-of-{} with {} malicious:",
             threshold, n_parties, n_malicious
         );
-        let frost = secp_frost::Frost::<Sha256, Deterministic<Sha256>>::default();
-        let (frost_key, secret_shares) =
-            frost.simulate_keygen(threshold, n_parties, &mut rand::thread_rng());
-        let xonly_frost_key = frost_key.into_xonly_key();
-
         let message = Message::plain("test", b"test");
-        let roast = coordinator::Coordinator::new(
-            frost.clone(),
-            xonly_frost_key.clone(),
-            message,
-            threshold,
-            n_parties,
-        );
+        // Each signer sends a fresh nonce every round until asked to sign
+        let (_, roast, mut signers, _) = setup(threshold, n_parties, message, rng);
 
         // use a boolean mask for which participants are malicious
         let mut malicious_mask = vec![true; n_malicious];
@@ -127,24 +2589,8 @@ mod tests {
             .map(|(i, _)| i)
             .collect();
 
-        // Create each signer session and create an initial nonce
-        let (mut signers, mut nonces): (Vec<_>, Vec<_>) = secret_shares
-            .into_iter()
-            .enumerate()
-            .map(|(i, secret_share)| {
-                signer::RoastSigner::new(
-                    rng,
-                    frost.clone(),
-                    xonly_frost_key.clone(),
-                    i,
-                    secret_share,
-                    message,
-                )
-            })
-            .unzip();
-
         let mut sig_shares = vec![];
-        let mut nonce_set: Vec<Option<Vec<(usize, Nonce)>>> = vec![None; n_parties + 1];
+        let mut sign_requests: Vec<Option<CoordinatorMessage>> = vec![None; n_parties + 1];
         let mut finished_signature = None;
         let mut n_rounds = 0;
 
@@ -152,36 +2598,57 @@ mod tests {
             n_rounds += 1;
             for signer_index in 0..n_parties {
                 // Check to see if this signer has recieved any nonces
-                let (sig, new_nonce) = match nonce_set[signer_index].clone() {
+                let message = match sign_requests[signer_index].clone() {
                     // If the signer has a nonce shared, sign and send sig as well as a new nonce
-                    Some(signing_nonces) => {
-                        let (mut sig, nonce) = signers[signer_index].sign(rng, signing_nonces);
+                    Some(sign_request) => {
+                        let message = signers[signer_index]
+                            .receive(rng, sign_request)
+                            .unwrap()
+                            .expect("signer replies to sign request");
                         // If we are malcious, send a bogus signature to disrupt signing process
-                        if malicious_indexes.contains(&signer_index) {
-                            sig = Scalar::random(rng).mark_zero().public();
+                        match message {
+                            SignerMessage::ShareAndNonce {
+                                message_id,
+                                session_id,
+                                nonce,
+                                ..
+                            } if malicious_indexes.contains(&signer_index) => {
+                                SignerMessage::ShareAndNonce {
+                                    message_id,
+                                    session_id,
+                                    signature_share: Scalar::random(rng).mark_zero().public(),
+                                    nonce,
+                                }
+                            }
+                            message => message,
                         }
-                        (Some(sig), nonce)
                     }
                     // Otherwise, just create a new nonce
-                    None => (None, signers[signer_index].new_nonce(rng).public()),
+                    None => SignerMessage::NonceOnly {
+                        message_id: signers[signer_index].message_id(),
+                        nonce: signers[signer_index].new_nonce(rng),
+                    },
                 };
                 // Send signature and our next nonce to ROAST
-                let response = roast.receive(signer_index, sig, new_nonce).unwrap();
-                nonces[signer_index] = new_nonce;
-
-                if response.combined_signature.is_some() {
-                    finished_signature = response.combined_signature;
-                    break;
-                }
+                let response = roast.receive(signer_index, message.clone()).unwrap();
 
+                let sign_request = match response.message {
+                    Some(CoordinatorMessage::Completed { signature }) => {
+                        finished_signature = Some(signature);
+                        break;
+                    }
+                    Some(sign_request @ CoordinatorMessage::SignRequest { .. }) => {
+                        Some(sign_request)
+                    }
+                    _ => None,
+                };
                 for index in response.recipients {
-                    nonce_set[index] = response.nonce_set.clone();
+                    sign_requests[index] = sign_request.clone();
                 }
 
-                if sig.is_some() {
+                if let Some(sig) = message.signature_share() {
                     sig_shares.push(sig);
                 }
-                // dbg!(&sig_shares);
             }
         }
         dbg!(&finished_signature, &n_rounds);